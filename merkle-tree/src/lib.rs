@@ -0,0 +1,193 @@
+//! Off-chain merkle tree for [locker::MerkleVestingDistributor].
+//!
+//! Reads `recipient,amount` rows from a CSV file and builds the root stored in the
+//! distributor together with the proof each recipient passes to `claim_merkle`.
+
+use anchor_lang::prelude::Pubkey;
+use locker::merkle::{hash_intermediate, hash_leaf};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MerkleTreeError {
+    #[error("csv error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("line {0}: invalid recipient {1}")]
+    InvalidRecipient(u64, String),
+
+    #[error("line {0}: invalid amount {1}")]
+    InvalidAmount(u64, String),
+
+    #[error("line {0}: expected recipient and amount columns")]
+    InvalidRow(u64),
+
+    #[error("duplicate recipient {0}")]
+    DuplicateRecipient(Pubkey),
+
+    #[error("tree has no recipients")]
+    Empty,
+
+    #[error("total amount overflows u64")]
+    TotalAmountOverflow,
+}
+
+/// A recipient's leaf and the proof of its inclusion in the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleTreeNode {
+    pub recipient: Pubkey,
+    pub total_amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    pub root: [u8; 32],
+    pub total_amount: u64,
+    pub nodes: Vec<MerkleTreeNode>,
+    indexes: HashMap<Pubkey, usize>,
+}
+
+impl MerkleTree {
+    pub fn new(entries: Vec<(Pubkey, u64)>) -> Result<Self, MerkleTreeError> {
+        if entries.is_empty() {
+            return Err(MerkleTreeError::Empty);
+        }
+
+        let mut indexes = HashMap::with_capacity(entries.len());
+        let mut total_amount = 0u64;
+        for (index, (recipient, amount)) in entries.iter().enumerate() {
+            if indexes.insert(*recipient, index).is_some() {
+                return Err(MerkleTreeError::DuplicateRecipient(*recipient));
+            }
+            total_amount = total_amount
+                .checked_add(*amount)
+                .ok_or(MerkleTreeError::TotalAmountOverflow)?;
+        }
+
+        let mut layer: Vec<[u8; 32]> = entries
+            .iter()
+            .map(|(recipient, amount)| hash_leaf(recipient, *amount))
+            .collect();
+        let mut proofs = vec![vec![]; entries.len()];
+        // position of each leaf's ancestor in the current layer
+        let mut positions: Vec<usize> = (0..entries.len()).collect();
+
+        while layer.len() > 1 {
+            for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+                // an odd node out is promoted without a sibling
+                if let Some(sibling) = layer.get(*position ^ 1) {
+                    proof.push(*sibling);
+                }
+                *position /= 2;
+            }
+            layer = layer
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_intermediate(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        let nodes = entries
+            .into_iter()
+            .zip(proofs)
+            .map(|((recipient, total_amount), proof)| MerkleTreeNode {
+                recipient,
+                total_amount,
+                proof,
+            })
+            .collect();
+
+        Ok(Self {
+            root: layer[0],
+            total_amount,
+            nodes,
+            indexes,
+        })
+    }
+
+    /// Build a tree from CSV rows of `recipient,amount` with a header line.
+    pub fn from_csv_reader(reader: impl Read) -> Result<Self, MerkleTreeError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        let mut entries = vec![];
+        for record in csv_reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line());
+            let (recipient, amount) = match (record.get(0), record.get(1)) {
+                (Some(recipient), Some(amount)) => (recipient, amount),
+                _ => return Err(MerkleTreeError::InvalidRow(line)),
+            };
+            let recipient = Pubkey::from_str(recipient)
+                .map_err(|_| MerkleTreeError::InvalidRecipient(line, recipient.to_owned()))?;
+            let amount = amount
+                .parse::<u64>()
+                .map_err(|_| MerkleTreeError::InvalidAmount(line, amount.to_owned()))?;
+            entries.push((recipient, amount));
+        }
+        Self::new(entries)
+    }
+
+    pub fn from_csv_path(path: impl AsRef<Path>) -> Result<Self, MerkleTreeError> {
+        Self::from_csv_reader(std::fs::File::open(path).map_err(csv::Error::from)?)
+    }
+
+    pub fn get_node(&self, recipient: &Pubkey) -> Option<&MerkleTreeNode> {
+        self.indexes.get(recipient).map(|index| &self.nodes[*index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use locker::merkle::verify_proof;
+
+    #[test]
+    fn test_every_proof_verifies() {
+        for num_recipients in 1..=33 {
+            let entries = (0..num_recipients)
+                .map(|amount| (Pubkey::new_unique(), amount))
+                .collect::<Vec<_>>();
+            let tree = MerkleTree::new(entries).unwrap();
+
+            for node in &tree.nodes {
+                let leaf = hash_leaf(&node.recipient, node.total_amount);
+                assert!(verify_proof(&node.proof, &tree.root, leaf));
+
+                let wrong_leaf = hash_leaf(&node.recipient, node.total_amount + 1);
+                assert!(!verify_proof(&node.proof, &tree.root, wrong_leaf));
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_csv_reader() {
+        let recipient_0 = Pubkey::new_unique();
+        let recipient_1 = Pubkey::new_unique();
+        let csv = format!("recipient,amount\n{recipient_0},100\n {recipient_1} , 250\n");
+
+        let tree = MerkleTree::from_csv_reader(csv.as_bytes()).unwrap();
+        assert_eq!(tree.total_amount, 350);
+        assert_eq!(tree.get_node(&recipient_1).unwrap().total_amount, 250);
+        assert!(tree.get_node(&Pubkey::new_unique()).is_none());
+
+        let csv = format!("recipient,amount\n{recipient_0},100\n{recipient_0},250\n");
+        assert!(matches!(
+            MerkleTree::from_csv_reader(csv.as_bytes()),
+            Err(MerkleTreeError::DuplicateRecipient(_))
+        ));
+
+        let csv = format!("recipient,amount\n{recipient_0},-1\n");
+        assert!(matches!(
+            MerkleTree::from_csv_reader(csv.as_bytes()),
+            Err(MerkleTreeError::InvalidAmount(2, _))
+        ));
+    }
+}
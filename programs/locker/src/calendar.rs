@@ -0,0 +1,186 @@
+//! UTC calendar helpers for month-based schedules
+//!
+//! Only `core` integer arithmetic is used, so the routines behave the same on-chain,
+//! off-chain and in `no_std` consumers. Dates are proleptic Gregorian and timestamps
+//! are seconds since the unix epoch.
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Days from 1970-01-01 to the date with the given year, month (1-12) and day (1-31).
+/// See <https://howardhinnant.github.io/date_algorithms.html>.
+pub const fn days_from_civil(year: u64, month: u64, day: u64) -> Option<u64> {
+    // years start in March so the leap day is the last day of the year
+    let year = if month <= 2 {
+        // January and February of year 0 belong to the year before, which is out of range
+        let Some(year) = year.checked_sub(1) else {
+            return None;
+        };
+        year
+    } else {
+        year
+    };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    // 0000-03-01 is 719468 days before the unix epoch
+    let Some(days) = era.checked_mul(146_097) else {
+        return None;
+    };
+    let Some(days) = days.checked_add(day_of_era) else {
+        return None;
+    };
+    days.checked_sub(719_468)
+}
+
+/// Year, month (1-12) and day (1-31) of the date `days` after 1970-01-01.
+pub const fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = year_of_era + era * 400;
+    let year = if month <= 2 { year + 1 } else { year };
+    (year, month, day)
+}
+
+// `u64::is_multiple_of` is newer than the rustc shipped with the Solana toolchain
+#[allow(clippy::manual_is_multiple_of)]
+pub const fn is_leap_year(year: u64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub const fn days_in_month(year: u64, month: u64) -> u64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Same time of day `months` calendar months after `timestamp`. The day is clamped to
+/// the end of shorter months, so Jan 31 plus one month is the last day of February.
+pub const fn add_months(timestamp: u64, months: u64) -> Option<u64> {
+    let (year, month, day) = civil_from_days(timestamp / SECONDS_PER_DAY);
+    let Some(total_months) = (year * 12 + month - 1).checked_add(months) else {
+        return None;
+    };
+    let new_year = total_months / 12;
+    let new_month = total_months % 12 + 1;
+    let max_day = days_in_month(new_year, new_month);
+    let new_day = if day < max_day { day } else { max_day };
+
+    let Some(days) = days_from_civil(new_year, new_month, new_day) else {
+        return None;
+    };
+    let Some(seconds) = days.checked_mul(SECONDS_PER_DAY) else {
+        return None;
+    };
+    seconds.checked_add(timestamp % SECONDS_PER_DAY)
+}
+
+/// Number of whole calendar months from `start` to `timestamp`, in the sense of
+/// [add_months]: the largest `n` with `add_months(start, n) <= timestamp`.
+pub const fn months_between(start: u64, timestamp: u64) -> u64 {
+    if timestamp <= start {
+        return 0;
+    }
+    let (start_year, start_month, _) = civil_from_days(start / SECONDS_PER_DAY);
+    let (year, month, _) = civil_from_days(timestamp / SECONDS_PER_DAY);
+    let months = (year * 12 + month) - (start_year * 12 + start_month);
+    // the last month may not be complete yet
+    match add_months(start, months) {
+        Some(end) if end <= timestamp => months,
+        _ => months - 1,
+    }
+}
+
+#[cfg(test)]
+mod calendar_test {
+    use super::*;
+
+    /// One full 400-year Gregorian cycle starting at the unix epoch.
+    const DAYS_PER_CYCLE: u64 = 146_097;
+
+    #[test]
+    fn test_civil_days_round_trip() {
+        let (mut year, mut month, mut day) = (1970, 1, 1);
+        for days in 0..DAYS_PER_CYCLE + 366 {
+            assert_eq!(civil_from_days(days), (year, month, day));
+            assert_eq!(days_from_civil(year, month, day), Some(days));
+
+            day += 1;
+            if day > days_in_month(year, month) {
+                day = 1;
+                month += 1;
+            }
+            if month > 12 {
+                month = 1;
+                year += 1;
+            }
+        }
+        assert_eq!((year, month, day), (2371, 1, 2));
+    }
+
+    #[test]
+    fn test_days_from_civil_out_of_range() {
+        assert_eq!(days_from_civil(0, 1, 1), None);
+        assert_eq!(days_from_civil(1969, 12, 31), None);
+        assert_eq!(days_from_civil(1970, 1, 1), Some(0));
+    }
+
+    #[test]
+    fn test_add_months() {
+        let timestamp = |year, month, day, seconds| {
+            days_from_civil(year, month, day).unwrap() * SECONDS_PER_DAY + seconds
+        };
+        assert_eq!(
+            add_months(timestamp(2024, 1, 31, 3600), 1),
+            Some(timestamp(2024, 2, 29, 3600))
+        );
+        assert_eq!(
+            add_months(timestamp(2023, 1, 31, 0), 1),
+            Some(timestamp(2023, 2, 28, 0))
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 2, 29, 0), 12),
+            Some(timestamp(2025, 2, 28, 0))
+        );
+        assert_eq!(
+            add_months(timestamp(2024, 11, 15, 59), 3),
+            Some(timestamp(2025, 2, 15, 59))
+        );
+        assert_eq!(add_months(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn test_months_between() {
+        // every start day of a 400-year cycle, with a varying time of day
+        for days in 0..DAYS_PER_CYCLE {
+            let start = days * SECONDS_PER_DAY + days % SECONDS_PER_DAY;
+            let (_, _, start_day) = civil_from_days(days);
+            for months in [0, 1, 2, 11, 12, 13, 25, 48] {
+                let end = add_months(start, months).unwrap();
+                let (_, _, end_day) = civil_from_days(end / SECONDS_PER_DAY);
+                assert_eq!(end % SECONDS_PER_DAY, start % SECONDS_PER_DAY);
+                assert!(end_day == start_day || end_day < start_day && end_day >= 28);
+
+                assert_eq!(months_between(start, end), months);
+                if months > 0 {
+                    assert_eq!(months_between(start, end - 1), months - 1);
+                }
+            }
+        }
+    }
+}
@@ -26,4 +26,145 @@ pub enum LockerError {
 
     #[msg("Invalid vesting start time")]
     InvalidVestingStartTime,
+
+    #[msg("Claiming is not finished")]
+    ClaimingIsNotFinished,
+
+    #[msg("Invalid creator token account")]
+    InvalidCreatorTokenAccount,
+
+    #[msg("Invalid cancel mode")]
+    InvalidCancelMode,
+
+    #[msg("Escrow is already cancelled")]
+    AlreadyCancelled,
+
+    #[msg("Mint extension is not supported")]
+    UnsupportedMint,
+
+    #[msg("Invalid deposit amount")]
+    InvalidDepositAmount,
+
+    #[msg("Invalid schedule kind")]
+    InvalidScheduleKind,
+
+    #[msg("Invalid number of schedule points")]
+    InvalidSchedulePointCount,
+
+    #[msg("Schedule timestamps are not strictly increasing")]
+    InvalidScheduleTimestamp,
+
+    #[msg("Schedule amounts are decreasing")]
+    InvalidScheduleAmount,
+
+    #[msg("Vesting schedule is missing")]
+    MissingVestingSchedule,
+
+    #[msg("Invalid batch accounts")]
+    InvalidBatchAccounts,
+
+    #[msg("Invalid escrow address")]
+    InvalidEscrowAddress,
+
+    #[msg("Invalid cliff unlock basis points")]
+    InvalidCliffUnlockBps,
+
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Invalid new recipient")]
+    InvalidNewRecipient,
+
+    #[msg("No pending recipient")]
+    NoPendingRecipient,
+
+    #[msg("No scheduled recipient change")]
+    NoScheduledRecipient,
+
+    #[msg("Recipient change is already effective")]
+    RecipientChangeAlreadyEffective,
+
+    #[msg("Recipient change is not effective yet")]
+    RecipientChangeNotEffective,
+
+    #[msg("Extension must raise the number of periods or the amount per period")]
+    InvalidExtendParameters,
+
+    #[msg("Extension would change an already unlocked amount")]
+    ExtendChangesUnlockedAmount,
+
+    #[msg("Metadata account cannot grow by more than MAX_PERMITTED_DATA_INCREASE")]
+    MetadataReallocTooLarge,
+
+    #[msg("Escrow metadata name is too long")]
+    MetadataNameTooLong,
+
+    #[msg("Escrow metadata description is too long")]
+    MetadataDescriptionTooLong,
+
+    #[msg("Creator email is too long")]
+    CreatorEmailTooLong,
+
+    #[msg("Recipient email is too long")]
+    RecipientEmailTooLong,
+
+    #[msg("Invalid creator email")]
+    InvalidCreatorEmail,
+
+    #[msg("Invalid recipient email")]
+    InvalidRecipientEmail,
+
+    #[msg("Escrow metadata uri is too long")]
+    MetadataUriTooLong,
+
+    #[msg("Metadata attribute key is empty or too long")]
+    InvalidMetadataAttributeKey,
+
+    #[msg("Metadata attribute value is too long")]
+    MetadataAttributeValueTooLong,
+
+    #[msg("Too many metadata attributes")]
+    TooManyMetadataAttributes,
+
+    #[msg("Metadata attribute not found")]
+    MetadataAttributeNotFound,
+
+    #[msg("Invalid time unit")]
+    InvalidTimeUnit,
+
+    #[msg("Invalid clock")]
+    InvalidClock,
+
+    #[msg("Invalid claim delegate")]
+    InvalidClaimDelegate,
+
+    #[msg("Claim delegate is not set")]
+    ClaimDelegateNotSet,
+
+    #[msg("Crank claims are not allowed for this escrow")]
+    CrankClaimNotAllowed,
+
+    #[msg("Recipient does not match the escrow")]
+    InvalidRecipient,
+
+    #[msg("Recipient token account mint does not match the escrow")]
+    InvalidRecipientTokenMint,
+
+    #[msg("Escrow token account already holds tokens")]
+    EscrowTokenNotEmpty,
+
+    #[msg("Recipient changes must be proposed and accepted")]
+    RecipientAcceptanceRequired,
+
+    #[msg("Recipient email cannot be updated with a scheduled recipient change")]
+    RecipientEmailWithScheduledChange,
+
+    #[msg("Escrow metadata is not in the legacy layout")]
+    MetadataNotLegacy,
+
+    #[msg("Merkle allocation is already fully claimed")]
+    MerkleAllocationFullyClaimed,
+
+    #[msg("A recipient change is already scheduled")]
+    RecipientChangeScheduled,
 }
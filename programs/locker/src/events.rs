@@ -1,3 +1,4 @@
+use crate::SchedulePoint;
 use anchor_lang::prelude::*;
 
 #[event]
@@ -9,6 +10,12 @@ pub struct EventCreateVestingEscrow {
     pub amount_per_period: u64,
     pub number_of_period: u64,
     pub update_recipient_mode: u8,
+    pub cancel_mode: u8,
+    pub schedule_kind: u8,
+    pub time_unit: u8,
+    pub allow_crank_claim: bool,
+    pub vault_kind: u8,
+    pub recipient_change_delay: u64,
     pub recipient: Pubkey,
     pub escrow: Pubkey,
 }
@@ -17,7 +24,54 @@ pub struct EventCreateVestingEscrow {
 pub struct EventClaim {
     pub amount: u64,
     pub current_ts: u64,
+    pub time_unit: u8,
     pub escrow: Pubkey,
+    pub claim_delegate: Option<Pubkey>,
+}
+
+#[event]
+pub struct EventSetClaimDelegate {
+    pub escrow: Pubkey,
+    pub old_claim_delegate: Pubkey,
+    pub claim_delegate: Pubkey,
+}
+
+#[event]
+pub struct EventRevokeClaimDelegate {
+    pub escrow: Pubkey,
+    pub claim_delegate: Pubkey,
+}
+
+#[event]
+pub struct EventUpdateVestingEscrowMetadata {
+    pub escrow: Pubkey,
+    pub escrow_metadata: Pubkey,
+    pub name: String,
+    pub description: String,
+    pub creator_email: String,
+    pub recipient_email: String,
+}
+
+#[event]
+pub struct EventSetVestingEscrowMetadataUri {
+    pub escrow: Pubkey,
+    pub escrow_metadata: Pubkey,
+    pub uri: String,
+}
+
+#[event]
+pub struct EventSetVestingEscrowMetadataAttribute {
+    pub escrow: Pubkey,
+    pub escrow_metadata: Pubkey,
+    pub key: String,
+    pub value: String,
+}
+
+#[event]
+pub struct EventRemoveVestingEscrowMetadataAttribute {
+    pub escrow: Pubkey,
+    pub escrow_metadata: Pubkey,
+    pub key: String,
 }
 
 #[event]
@@ -27,3 +81,104 @@ pub struct EventUpdateVestingEscrowRecipient {
     pub new_recipient: Pubkey,
     pub signer: Pubkey,
 }
+
+#[event]
+pub struct EventCloseVestingEscrow {
+    pub escrow: Pubkey,
+    pub creator: Pubkey,
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct EventCancelVestingEscrow {
+    pub escrow: Pubkey,
+    pub signer: Pubkey,
+    pub claimable_amount: u64,
+    pub remaining_amount: u64,
+    pub cancelled_at: u64,
+    pub time_unit: u8,
+}
+
+#[event]
+pub struct EventCreateVestingSchedule {
+    pub escrow: Pubkey,
+    pub vesting_schedule: Pubkey,
+    pub points: Vec<SchedulePoint>,
+}
+
+#[event]
+pub struct EventCreateMerkleVestingDistributor {
+    pub distributor: Pubkey,
+    pub root: [u8; 32],
+    pub total_amount: u64,
+    pub vesting_start_time: u64,
+    pub cliff_time: u64,
+    pub frequency: u64,
+    pub number_of_period: u64,
+    pub cliff_unlock_bps: u16,
+    pub schedule_kind: u8,
+}
+
+#[event]
+pub struct EventClaimMerkle {
+    pub amount: u64,
+    pub current_ts: u64,
+    pub distributor: Pubkey,
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct EventProposeVestingEscrowRecipient {
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub pending_recipient: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct EventAcceptVestingEscrowRecipient {
+    pub escrow: Pubkey,
+    pub old_recipient: Pubkey,
+    pub new_recipient: Pubkey,
+}
+
+#[event]
+pub struct EventCancelVestingEscrowRecipientProposal {
+    pub escrow: Pubkey,
+    pub pending_recipient: Pubkey,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct EventScheduleVestingEscrowRecipient {
+    pub escrow: Pubkey,
+    pub recipient: Pubkey,
+    pub scheduled_recipient: Pubkey,
+    pub effective_at: u64,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct EventVetoVestingEscrowRecipient {
+    pub escrow: Pubkey,
+    pub scheduled_recipient: Pubkey,
+    pub effective_at: u64,
+    pub signer: Pubkey,
+}
+
+#[event]
+pub struct EventApplyVestingEscrowRecipient {
+    pub escrow: Pubkey,
+    pub old_recipient: Pubkey,
+    pub new_recipient: Pubkey,
+}
+
+#[event]
+pub struct EventExtendVestingEscrow {
+    pub escrow: Pubkey,
+    pub old_number_of_period: u64,
+    pub old_amount_per_period: u64,
+    pub number_of_period: u64,
+    pub amount_per_period: u64,
+    pub deposit_amount: u64,
+}
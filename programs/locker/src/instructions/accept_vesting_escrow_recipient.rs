@@ -0,0 +1,47 @@
+use crate::*;
+
+/// Accounts for [locker::accept_vesting_escrow_recipient].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct AcceptVestingEscrowRecipientCtx<'info> {
+    /// Escrow.
+    #[account(mut, has_one = pending_recipient @ LockerError::NotPermitToDoThisAction)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Proposed recipient.
+    pub pending_recipient: Signer<'info>,
+}
+
+pub fn handle_accept_vesting_escrow_recipient(
+    ctx: Context<AcceptVestingEscrowRecipientCtx>,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    require!(
+        escrow.has_proposed_recipient(),
+        LockerError::NoPendingRecipient
+    );
+
+    let old_recipient = escrow.recipient;
+    let new_recipient = escrow.pending_recipient;
+
+    // an accepted proposal still waits out the escrow's recipient change delay
+    let effective_at = escrow.change_recipient(new_recipient, current_ts)?;
+    if escrow.has_scheduled_recipient() {
+        emit_cpi!(EventScheduleVestingEscrowRecipient {
+            escrow: ctx.accounts.escrow.key(),
+            recipient: old_recipient,
+            scheduled_recipient: new_recipient,
+            effective_at,
+            signer: new_recipient,
+        });
+        return Ok(());
+    }
+
+    emit_cpi!(EventAcceptVestingEscrowRecipient {
+        escrow: ctx.accounts.escrow.key(),
+        old_recipient,
+        new_recipient,
+    });
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::*;
+
+/// Accounts for [locker::apply_vesting_escrow_recipient].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct ApplyVestingEscrowRecipientCtx<'info> {
+    /// Escrow.
+    #[account(mut)]
+    pub escrow: Account<'info, VestingEscrow>,
+}
+
+pub fn handle_apply_vesting_escrow_recipient(
+    ctx: Context<ApplyVestingEscrowRecipientCtx>,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    require!(
+        escrow.has_scheduled_recipient(),
+        LockerError::NoScheduledRecipient
+    );
+
+    let old_recipient = escrow
+        .apply_scheduled_recipient(current_ts)
+        .ok_or(LockerError::RecipientChangeNotEffective)?;
+
+    emit_cpi!(EventApplyVestingEscrowRecipient {
+        escrow: ctx.accounts.escrow.key(),
+        old_recipient,
+        new_recipient: ctx.accounts.escrow.recipient,
+    });
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use crate::safe_math::SafeMath;
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Accounts for [locker::cancel_vesting_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelVestingEscrowCtx<'info> {
+    /// Escrow.
+    #[account(mut, has_one = token_mint)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+        constraint = escrow_token.key() == escrow.get_escrow_token_address(&escrow.key(), &token_program.key())? @ LockerError::InvalidEscrowTokenAddress
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator token account, receives the locked remainder.
+    #[account(
+        mut,
+        constraint = creator_token.mint == escrow.token_mint @ LockerError::InvalidCreatorTokenAccount,
+        constraint = creator_token.owner == escrow.creator @ LockerError::InvalidCreatorTokenAccount
+    )]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Recipient token account, receives the unlocked amount. Its owner is checked in the
+    /// handler, once a due recipient change has been applied.
+    #[account(
+        mut,
+        constraint = recipient_token.mint == escrow.token_mint @ LockerError::InvalidRecipientTokenAccount
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Signer.
+    pub signer: Signer<'info>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CancelVestingEscrowCtx<'info> {
+    fn transfer_from_escrow(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        let escrow = &self.escrow;
+        let escrow_seeds = escrow_seeds!(escrow);
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow_token.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to,
+                    authority: self.escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handle_cancel_vesting_escrow(ctx: Context<CancelVestingEscrowCtx>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+
+    // a due change decides who gets the unlocked amount and who may cancel
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        ctx.accounts.recipient_token.owner == escrow.recipient,
+        LockerError::InvalidRecipientTokenAccount
+    );
+
+    let signer = ctx.accounts.signer.key();
+    escrow.validate_cancel_signer(signer)?;
+
+    require!(!escrow.is_cancelled(), LockerError::AlreadyCancelled);
+
+    let claimable_amount = escrow
+        .get_claimable_amount_with_schedule(current_ts, ctx.accounts.vesting_schedule.as_deref())?;
+    escrow.accumulate_claimed_amount(claimable_amount)?;
+    escrow.cancel(current_ts);
+
+    let remaining_amount = ctx
+        .accounts
+        .escrow_token
+        .amount
+        .safe_sub(claimable_amount)?;
+
+    ctx.accounts.transfer_from_escrow(
+        ctx.accounts.recipient_token.to_account_info(),
+        claimable_amount,
+    )?;
+    ctx.accounts.transfer_from_escrow(
+        ctx.accounts.creator_token.to_account_info(),
+        remaining_amount,
+    )?;
+
+    emit_cpi!(EventCancelVestingEscrow {
+        escrow: ctx.accounts.escrow.key(),
+        signer,
+        claimable_amount,
+        remaining_amount,
+        cancelled_at: current_ts,
+        time_unit: ctx.accounts.escrow.time_unit,
+    });
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::*;
+
+/// Accounts for [locker::cancel_vesting_escrow_recipient_proposal].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct CancelVestingEscrowRecipientProposalCtx<'info> {
+    /// Escrow.
+    #[account(mut)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Signer.
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_cancel_vesting_escrow_recipient_proposal(
+    ctx: Context<CancelVestingEscrowRecipientProposalCtx>,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let signer = ctx.accounts.signer.key();
+    require!(
+        escrow.has_proposed_recipient(),
+        LockerError::NoPendingRecipient
+    );
+
+    // the proposed recipient can always decline
+    if signer != escrow.pending_recipient {
+        escrow.validate_update_recipient_signer(signer)?;
+    }
+
+    let pending_recipient = escrow.pending_recipient;
+    escrow.cancel_pending_recipient();
+
+    emit_cpi!(EventCancelVestingEscrowRecipientProposal {
+        escrow: ctx.accounts.escrow.key(),
+        pending_recipient,
+        signer,
+    });
+    Ok(())
+}
@@ -1,60 +1,147 @@
 use crate::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, AssociatedToken, Create,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimCtx<'info> {
-    #[account(mut, has_one = recipient)]
+    #[account(mut, has_one = token_mint)]
     pub escrow: Account<'info, VestingEscrow>,
 
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
     #[account(mut)]
-    pub escrow_token: Box<Account<'info, TokenAccount>>,
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub recipient: Signer<'info>,
 
-    #[account(mut, constraint = recipient_token.key() != escrow_token.key() @ LockerError::InvalidRecipientTokenAccount)]
-    pub recipient_token: Box<Account<'info, TokenAccount>>,
+    /// CHECK: a token account of the recipient, or their ATA to be created on the first
+    /// claim, checked by the handler so that a token account of another mint is reported as such
+    #[account(
+        mut,
+        constraint = recipient_token.key() != escrow_token.key() @ LockerError::InvalidRecipientTokenAccount
+    )]
+    pub recipient_token: UncheckedAccount<'info>,
 
     /// Token program.
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // system program
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> ClaimCtx<'info> {
+    /// Accept an existing token account of the recipient, or create the recipient's ATA
+    /// at their expense when it does not exist yet.
+    fn init_recipient_token(&self) -> Result<()> {
+        let recipient_token = self.recipient_token.to_account_info();
+        if !recipient_token.data_is_empty() {
+            require!(
+                *recipient_token.owner == self.token_program.key(),
+                LockerError::InvalidRecipientTokenAccount
+            );
+            let data = recipient_token.try_borrow_data()?;
+            let token_account = TokenAccount::try_deserialize(&mut &data[..])?;
+            require!(
+                token_account.mint == self.escrow.token_mint,
+                LockerError::InvalidRecipientTokenMint
+            );
+            require!(
+                token_account.owner == self.recipient.key(),
+                LockerError::InvalidRecipientTokenAccount
+            );
+            return Ok(());
+        }
+        require!(
+            recipient_token.key()
+                == get_associated_token_address_with_program_id(
+                    &self.recipient.key(),
+                    &self.escrow.token_mint,
+                    &self.token_program.key(),
+                ),
+            LockerError::InvalidRecipientTokenAccount
+        );
+
+        anchor_spl::associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.recipient.to_account_info(),
+                associated_token: recipient_token,
+                authority: self.recipient.to_account_info(),
+                mint: self.token_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+
     fn transfer_to_recipient(&self, amount: u64) -> Result<()> {
         let escrow = &self.escrow;
         let escrow_seeds = escrow_seeds!(escrow);
-        anchor_spl::token::transfer(
+        anchor_spl::token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: self.escrow_token.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
                     to: self.recipient_token.to_account_info(),
                     authority: self.escrow.to_account_info(),
                 },
                 &[&escrow_seeds[..]],
             ),
             amount,
+            self.token_mint.decimals,
         )?;
         Ok(())
     }
 }
 
 pub fn handle_claim(ctx: Context<ClaimCtx>, max_amount: u64) -> Result<()> {
-    let current_ts = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.init_recipient_token()?;
+
     let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
 
-    let escrow_token = anchor_spl::associated_token::get_associated_token_address(
-        &escrow.key(),
-        &escrow.token_mint,
+    // the old recipient keeps claiming until a scheduled change is due
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        escrow.recipient == ctx.accounts.recipient.key(),
+        LockerError::NotPermitToDoThisAction
     );
 
+    let escrow_token =
+        escrow.get_escrow_token_address(&escrow.key(), &ctx.accounts.token_program.key())?;
+
     require!(
         escrow_token == ctx.accounts.escrow_token.key(),
         LockerError::InvalidEscrowTokenAddress
     );
 
-    let claimable_amount = escrow.get_claimable_amount(current_ts)?;
+    let claimable_amount = escrow
+        .get_claimable_amount_with_schedule(current_ts, ctx.accounts.vesting_schedule.as_deref())?;
 
     let amount = claimable_amount.min(max_amount);
     escrow.accumulate_claimed_amount(amount)?;
@@ -75,7 +162,9 @@ pub fn handle_claim(ctx: Context<ClaimCtx>, max_amount: u64) -> Result<()> {
     emit_cpi!(EventClaim {
         amount,
         current_ts,
+        time_unit: ctx.accounts.escrow.time_unit,
         escrow: ctx.accounts.escrow.key(),
+        claim_delegate: None,
     });
     Ok(())
 }
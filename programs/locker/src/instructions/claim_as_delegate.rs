@@ -0,0 +1,113 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Accounts for [locker::claim_as_delegate].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimAsDelegateCtx<'info> {
+    #[account(mut, has_one = token_mint)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Claim delegate set by the recipient.
+    pub claim_delegate: Signer<'info>,
+
+    /// The recipient's associated token account, the only account a delegate can claim to.
+    #[account(mut)]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimAsDelegateCtx<'info> {
+    fn transfer_to_recipient(&self, amount: u64) -> Result<()> {
+        let escrow = &self.escrow;
+        let escrow_seeds = escrow_seeds!(escrow);
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow_token.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.recipient_token.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handle_claim_as_delegate(ctx: Context<ClaimAsDelegateCtx>, max_amount: u64) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+
+    // a due recipient change also revokes the old recipient's delegate
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    let claim_delegate = ctx.accounts.claim_delegate.key();
+    require!(
+        escrow.has_claim_delegate() && escrow.claim_delegate == claim_delegate,
+        LockerError::InvalidClaimDelegate
+    );
+
+    let token_program = ctx.accounts.token_program.key();
+    let escrow_token = escrow.get_escrow_token_address(&escrow.key(), &token_program)?;
+    require!(
+        escrow_token == ctx.accounts.escrow_token.key(),
+        LockerError::InvalidEscrowTokenAddress
+    );
+
+    let recipient_token =
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &escrow.recipient,
+            &escrow.token_mint,
+            &token_program,
+        );
+    require!(
+        recipient_token == ctx.accounts.recipient_token.key(),
+        LockerError::InvalidRecipientTokenAccount
+    );
+
+    let claimable_amount = escrow
+        .get_claimable_amount_with_schedule(current_ts, ctx.accounts.vesting_schedule.as_deref())?;
+
+    let amount = claimable_amount.min(max_amount);
+    escrow.accumulate_claimed_amount(amount)?;
+
+    ctx.accounts.transfer_to_recipient(amount)?;
+
+    emit_cpi!(EventClaim {
+        amount,
+        current_ts,
+        time_unit: ctx.accounts.escrow.time_unit,
+        escrow: ctx.accounts.escrow.key(),
+        claim_delegate: Some(claim_delegate),
+    });
+    Ok(())
+}
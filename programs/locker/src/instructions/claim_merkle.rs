@@ -0,0 +1,121 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Accounts for [locker::claim_merkle].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimMerkleCtx<'info> {
+    #[account(mut, has_one = token_mint)]
+    pub distributor: Box<Account<'info, MerkleVestingDistributor>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            b"merkle_claim_status".as_ref(),
+            distributor.key().as_ref(),
+            recipient.key().as_ref(),
+        ],
+        bump,
+        payer = recipient,
+        space = 8 + MerkleClaimStatus::INIT_SPACE
+    )]
+    pub claim_status: Box<Account<'info, MerkleClaimStatus>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = recipient_token.mint == token_mint.key() @ LockerError::InvalidRecipientTokenAccount
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimMerkleCtx<'info> {
+    fn transfer_to_recipient(&self, amount: u64) -> Result<()> {
+        let distributor = &self.distributor;
+        let distributor_seeds = merkle_distributor_seeds!(distributor);
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.vault.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.recipient_token.to_account_info(),
+                    authority: self.distributor.to_account_info(),
+                },
+                &[&distributor_seeds[..]],
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handle_claim_merkle(
+    ctx: Context<ClaimMerkleCtx>,
+    total_amount: u64,
+    proof: Vec<[u8; 32]>,
+    max_amount: u64,
+) -> Result<()> {
+    let recipient = ctx.accounts.recipient.key();
+
+    let leaf = merkle::hash_leaf(&recipient, total_amount);
+    require!(
+        merkle::verify_proof(&proof, &ctx.accounts.distributor.root, leaf),
+        LockerError::InvalidMerkleProof
+    );
+
+    let claim_status = &mut ctx.accounts.claim_status;
+    // first claim of this recipient
+    if claim_status.distributor == Pubkey::default() {
+        claim_status.distributor = ctx.accounts.distributor.key();
+        claim_status.recipient = recipient;
+        claim_status.total_amount = total_amount;
+    }
+    // a recipient's leaf can only be claimed until its whole allocation is out
+    require!(
+        claim_status.total_claimed_amount < claim_status.total_amount,
+        LockerError::MerkleAllocationFullyClaimed
+    );
+
+    let escrow = ctx.accounts.distributor.get_vesting_escrow(
+        recipient,
+        claim_status.total_amount,
+        claim_status.total_claimed_amount,
+    )?;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    let claimable_amount = escrow.get_claimable_amount(current_ts)?;
+
+    let amount = claimable_amount.min(max_amount);
+    claim_status.accumulate_claimed_amount(amount)?;
+    ctx.accounts.distributor.accumulate_claimed_amount(amount)?;
+
+    ctx.accounts.transfer_to_recipient(amount)?;
+
+    emit_cpi!(EventClaimMerkle {
+        amount,
+        current_ts,
+        distributor: ctx.accounts.distributor.key(),
+        recipient,
+    });
+    Ok(())
+}
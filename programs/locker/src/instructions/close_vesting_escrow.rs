@@ -0,0 +1,155 @@
+use crate::*;
+use anchor_spl::token_interface::{
+    CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+/// Accounts for [locker::close_vesting_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVestingEscrowCtx<'info> {
+    /// Escrow.
+    #[account(mut, has_one = creator, has_one = token_mint, close = creator)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// CHECK: escrow metadata PDA, closed in the handler if it was created. Always
+    /// required, so the creator cannot leave the metadata rent stranded.
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_metadata".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_metadata: UncheckedAccount<'info>,
+
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        mut,
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow,
+        close = creator
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+
+    #[account(mut, mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+        constraint = escrow_token.key() == escrow.get_escrow_token_address(&escrow.key(), &token_program.key())? @ LockerError::InvalidEscrowTokenAddress
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator of the escrow.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Creator token account, receives any tokens left in the escrow.
+    #[account(mut, constraint = creator_token.mint == escrow.token_mint @ LockerError::InvalidCreatorTokenAccount)]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseVestingEscrowCtx<'info> {
+    fn transfer_to_creator(&self, amount: u64) -> Result<()> {
+        let escrow = &self.escrow;
+        let escrow_seeds = escrow_seeds!(escrow);
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow_token.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.creator_token.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+
+    fn harvest_withheld_fees(&self) -> Result<()> {
+        anchor_spl::token_interface::harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                },
+            ),
+            vec![self.escrow_token.to_account_info()],
+        )?;
+        Ok(())
+    }
+
+    fn close_escrow_token(&self) -> Result<()> {
+        let escrow = &self.escrow;
+        let escrow_seeds = escrow_seeds!(escrow);
+        anchor_spl::token_interface::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.escrow_token.to_account_info(),
+                destination: self.creator.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            &[&escrow_seeds[..]],
+        ))?;
+        Ok(())
+    }
+
+    /// Close the metadata the way `close = creator` would, if it was ever created.
+    fn close_escrow_metadata(&self) -> Result<()> {
+        let escrow_metadata = self.escrow_metadata.to_account_info();
+        // only this program can create an account at the metadata PDA
+        if *escrow_metadata.owner != crate::ID {
+            return Ok(());
+        }
+        self.creator.add_lamports(escrow_metadata.lamports())?;
+        escrow_metadata.sub_lamports(escrow_metadata.lamports())?;
+        escrow_metadata.assign(&System::id());
+        escrow_metadata.realloc(0, false)?;
+        Ok(())
+    }
+}
+
+pub fn handle_close_vesting_escrow(ctx: Context<CloseVestingEscrowCtx>) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+
+    // nothing is left to claim once the schedule, frozen if cancelled, has fully unlocked
+    let claimable_amount = escrow
+        .get_claimable_amount_with_schedule(u64::MAX, ctx.accounts.vesting_schedule.as_deref())?;
+    require!(claimable_amount == 0, LockerError::ClaimingIsNotFinished);
+
+    // anyone can send tokens to the escrow ATA, sweep them back so it can be closed
+    let remaining_amount = ctx.accounts.escrow_token.amount;
+    if remaining_amount > 0 {
+        ctx.accounts.transfer_to_creator(remaining_amount)?;
+    }
+    // token accounts holding withheld transfer fees cannot be closed
+    if token2022::get_withheld_amount(&ctx.accounts.escrow_token.to_account_info())? > 0 {
+        ctx.accounts.harvest_withheld_fees()?;
+    }
+    ctx.accounts.close_escrow_token()?;
+
+    ctx.accounts.close_escrow_metadata()?;
+
+    emit_cpi!(EventCloseVestingEscrow {
+        escrow: ctx.accounts.escrow.key(),
+        creator: ctx.accounts.creator.key(),
+        remaining_amount,
+    });
+    Ok(())
+}
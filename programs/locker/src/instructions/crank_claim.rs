@@ -0,0 +1,115 @@
+use crate::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+
+/// Accounts for [locker::crank_claim].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CrankClaimCtx<'info> {
+    #[account(mut, has_one = token_mint)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient of the escrow, checked once any due recipient change is applied
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The recipient's associated token account, created at the cranker's expense if missing.
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CrankClaimCtx<'info> {
+    fn transfer_to_recipient(&self, amount: u64) -> Result<()> {
+        let escrow = &self.escrow;
+        let escrow_seeds = escrow_seeds!(escrow);
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.escrow_token.to_account_info(),
+                    mint: self.token_mint.to_account_info(),
+                    to: self.recipient_token.to_account_info(),
+                    authority: self.escrow.to_account_info(),
+                },
+                &[&escrow_seeds[..]],
+            ),
+            amount,
+            self.token_mint.decimals,
+        )?;
+        Ok(())
+    }
+}
+
+pub fn handle_crank_claim(ctx: Context<CrankClaimCtx>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    require!(escrow.allow_crank_claim, LockerError::CrankClaimNotAllowed);
+
+    // pay whoever is the recipient once a due change is applied
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        escrow.recipient == ctx.accounts.recipient.key(),
+        LockerError::InvalidRecipient
+    );
+
+    let escrow_token =
+        escrow.get_escrow_token_address(&escrow.key(), &ctx.accounts.token_program.key())?;
+    require!(
+        escrow_token == ctx.accounts.escrow_token.key(),
+        LockerError::InvalidEscrowTokenAddress
+    );
+
+    let amount = escrow
+        .get_claimable_amount_with_schedule(current_ts, ctx.accounts.vesting_schedule.as_deref())?;
+    escrow.accumulate_claimed_amount(amount)?;
+
+    ctx.accounts.transfer_to_recipient(amount)?;
+
+    emit_cpi!(EventClaim {
+        amount,
+        current_ts,
+        time_unit: ctx.accounts.escrow.time_unit,
+        escrow: ctx.accounts.escrow.key(),
+        claim_delegate: None,
+    });
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use arbitrary::Arbitrary;
+
+/// Accounts for [locker::create_merkle_vesting_distributor].
+#[derive(AnchorSerialize, AnchorDeserialize, Arbitrary, Debug)]
+pub struct CreateMerkleVestingDistributorParameters {
+    pub root: [u8; 32],
+    pub total_amount: u64,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=1_000_000)
+    )]
+    pub vesting_start_time: u64,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(1_000_000..=u64::MAX)
+    )]
+    pub cliff_time: u64,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(1..=u64::MAX)
+    )]
+    pub frequency: u64,
+    pub number_of_period: u64,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=MAX_BASIS_POINTS)
+    )]
+    pub cliff_unlock_bps: u16,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=4)
+    )]
+    pub schedule_kind: u8,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMerkleVestingDistributorCtx<'info> {
+    #[account(mut)]
+    pub base: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            b"merkle_distributor".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + MerkleVestingDistributor::INIT_SPACE
+    )]
+    pub distributor: Box<Account<'info, MerkleVestingDistributor>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_merkle_vesting_distributor(
+    ctx: Context<CreateMerkleVestingDistributorCtx>,
+    params: &CreateMerkleVestingDistributorParameters,
+) -> Result<()> {
+    let &CreateMerkleVestingDistributorParameters {
+        root,
+        total_amount,
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        number_of_period,
+        cliff_unlock_bps,
+        schedule_kind,
+    } = params;
+
+    require!(
+        cliff_time >= vesting_start_time,
+        LockerError::InvalidVestingStartTime
+    );
+
+    require!(
+        matches!(
+            ScheduleKind::try_from(schedule_kind),
+            Ok(ScheduleKind::Periodic
+                | ScheduleKind::Linear
+                | ScheduleKind::PeriodicFromStart
+                | ScheduleKind::CalendarMonthly)
+        ),
+        LockerError::InvalidScheduleKind,
+    );
+
+    require!(
+        cliff_unlock_bps <= MAX_BASIS_POINTS,
+        LockerError::InvalidCliffUnlockBps
+    );
+
+    require!(frequency != 0, LockerError::FrequencyIsZero);
+
+    token2022::validate_mint(&ctx.accounts.token_mint)?;
+
+    let distributor = &mut ctx.accounts.distributor;
+    distributor.root = root;
+    distributor.token_mint = ctx.accounts.token_mint.key();
+    distributor.creator = ctx.accounts.sender.key();
+    distributor.base = ctx.accounts.base.key();
+    distributor.distributor_bump = ctx.bumps.distributor;
+    distributor.schedule_kind = schedule_kind;
+    distributor.cliff_unlock_bps = cliff_unlock_bps;
+    distributor.vesting_start_time = vesting_start_time;
+    distributor.cliff_time = cliff_time;
+    distributor.frequency = frequency;
+    distributor.number_of_period = number_of_period;
+    distributor.total_amount = total_amount;
+
+    transfer_deposit_to_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.sender,
+        &ctx.accounts.sender_token,
+        &mut ctx.accounts.vault,
+        total_amount,
+    )?;
+
+    emit_cpi!(EventCreateMerkleVestingDistributor {
+        distributor: ctx.accounts.distributor.key(),
+        root,
+        total_amount,
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        number_of_period,
+        cliff_unlock_bps,
+        schedule_kind,
+    });
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::safe_math::SafeMath;
 use crate::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
 use arbitrary::Arbitrary;
 
 /// Accounts for [locker::create_vesting_escrow].
@@ -25,9 +26,65 @@ pub struct CreateVestingEscrowParameters {
         with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=3)
     )]
     pub update_recipient_mode: u8,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=3)
+    )]
+    pub cancel_mode: u8,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=4)
+    )]
+    pub schedule_kind: u8,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=1)
+    )]
+    pub time_unit: u8,
+    pub allow_crank_claim: bool,
+    pub recipient_change_delay: u64,
 }
 
 impl CreateVestingEscrowParameters {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.cliff_time >= self.vesting_start_time,
+            LockerError::InvalidVestingStartTime
+        );
+
+        require!(
+            UpdateRecipientMode::try_from(self.update_recipient_mode).is_ok(),
+            LockerError::InvalidUpdateRecipientMode,
+        );
+
+        require!(
+            CancelMode::try_from(self.cancel_mode).is_ok(),
+            LockerError::InvalidCancelMode,
+        );
+
+        // custom schedules are created through create_vesting_escrow_with_schedule
+        require!(
+            matches!(
+                ScheduleKind::try_from(self.schedule_kind),
+                Ok(ScheduleKind::Periodic
+                    | ScheduleKind::Linear
+                    | ScheduleKind::PeriodicFromStart
+                    | ScheduleKind::CalendarMonthly)
+            ),
+            LockerError::InvalidScheduleKind,
+        );
+
+        // calendar months only make sense against unix timestamps
+        let time_unit =
+            TimeUnit::try_from(self.time_unit).map_err(|_| LockerError::InvalidTimeUnit)?;
+        require!(
+            time_unit == TimeUnit::UnixTimestamp
+                || self.schedule_kind != u8::from(ScheduleKind::CalendarMonthly),
+            LockerError::InvalidTimeUnit,
+        );
+
+        require!(self.frequency != 0, LockerError::FrequencyIsZero);
+
+        Ok(())
+    }
+
     pub fn get_total_deposit_amount(&self) -> Result<u64> {
         let total_amount = self
             .cliff_unlock_amount
@@ -54,24 +111,32 @@ pub struct CreateVestingEscrowCtx<'info> {
     )]
     pub escrow: Account<'info, VestingEscrow>,
 
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow ATA, created here. An existing one is accepted only while it holds no tokens.
     #[account(
-        mut,
-        associated_token::mint = sender_token.mint,
-        associated_token::authority = escrow
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
     )]
-    pub escrow_token: Box<Account<'info, TokenAccount>>,
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub sender: Signer<'info>,
 
-    #[account(mut)]
-    pub sender_token: Box<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: recipient account
     pub recipient: UncheckedAccount<'info>,
 
     /// Token program.
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
 
     // system program
     pub system_program: Program<'info, System>,
@@ -89,19 +154,16 @@ pub fn handle_create_vesting_escrow(
         amount_per_period,
         number_of_period,
         update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        recipient_change_delay,
     } = params;
 
-    require!(
-        cliff_time >= vesting_start_time,
-        LockerError::InvalidVestingStartTime
-    );
+    params.validate()?;
 
-    require!(
-        UpdateRecipientMode::try_from(update_recipient_mode).is_ok(),
-        LockerError::InvalidUpdateRecipientMode,
-    );
-
-    require!(frequency != 0, LockerError::FrequencyIsZero);
+    token2022::validate_mint(&ctx.accounts.token_mint)?;
 
     let escrow = &mut ctx.accounts.escrow;
 
@@ -113,22 +175,29 @@ pub fn handle_create_vesting_escrow(
         amount_per_period,
         number_of_period,
         ctx.accounts.recipient.key(),
-        ctx.accounts.sender_token.mint,
+        ctx.accounts.token_mint.key(),
         ctx.accounts.sender.key(),
         ctx.accounts.base.key(),
         ctx.bumps.escrow,
         update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        recipient_change_delay,
     );
 
-    anchor_spl::token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.sender_token.to_account_info(),
-                to: ctx.accounts.escrow_token.to_account_info(),
-                authority: ctx.accounts.sender.to_account_info(),
-            },
-        ),
+    // a stray balance would be swept to the creator without ever being accounted for
+    require!(
+        ctx.accounts.escrow_token.amount == 0,
+        LockerError::EscrowTokenNotEmpty
+    );
+    transfer_deposit_to_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.sender,
+        &ctx.accounts.sender_token,
+        &mut ctx.accounts.escrow_token,
         params.get_total_deposit_amount()?,
     )?;
 
@@ -141,7 +210,50 @@ pub fn handle_create_vesting_escrow(
         recipient: ctx.accounts.recipient.key(),
         escrow: ctx.accounts.escrow.key(),
         update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        vault_kind: VaultKind::AssociatedToken.into(),
+        recipient_change_delay,
         vesting_start_time,
     });
     Ok(())
 }
+
+/// Transfer `total_deposit_amount` from the sender to the escrow. The sender pays any
+/// transfer fee, so the escrow receives exactly what its schedule pays out.
+pub fn transfer_deposit_to_escrow<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    sender: &Signer<'info>,
+    sender_token: &InterfaceAccount<'info, TokenAccount>,
+    escrow_token: &mut InterfaceAccount<'info, TokenAccount>,
+    total_deposit_amount: u64,
+) -> Result<()> {
+    let transfer_amount =
+        token2022::calculate_transfer_fee_included_amount(token_mint, total_deposit_amount)?;
+    let pre_escrow_token_amount = escrow_token.amount;
+
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: sender_token.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: escrow_token.to_account_info(),
+                authority: sender.to_account_info(),
+            },
+        ),
+        transfer_amount,
+        token_mint.decimals,
+    )?;
+
+    escrow_token.reload()?;
+    let received_amount = escrow_token.amount.safe_sub(pre_escrow_token_amount)?;
+    require!(
+        received_amount == total_deposit_amount,
+        LockerError::InvalidDepositAmount
+    );
+    Ok(())
+}
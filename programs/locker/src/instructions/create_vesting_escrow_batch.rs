@@ -0,0 +1,231 @@
+use crate::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::system_program::{Allocate, Assign, CreateAccount, Transfer};
+use anchor_spl::associated_token::{AssociatedToken, Create};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Accounts for [locker::create_vesting_escrow_batch].
+///
+/// Remaining accounts are `(recipient, escrow, escrow_token)` triples, one per entry in
+/// `params`. The escrow of entry `i` is derived from [get_batch_base] of `base` and `i`,
+/// and its ATA is created here like in [locker::create_vesting_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVestingEscrowBatchCtx<'info> {
+    pub base: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+/// Base key of the `index`-th escrow of a batch, so a single `base` signer covers the batch.
+pub fn get_batch_base(base: &Pubkey, index: u64) -> Pubkey {
+    Pubkey::new_from_array(
+        hashv(&[b"batch_base".as_ref(), base.as_ref(), &index.to_le_bytes()]).to_bytes(),
+    )
+}
+
+impl<'info> CreateVestingEscrowBatchCtx<'info> {
+    /// Create the escrow PDA the way an `init` constraint would, tolerating a pre-funded address.
+    fn create_escrow_account(
+        &self,
+        escrow: &AccountInfo<'info>,
+        signer_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let space = 8 + VestingEscrow::INIT_SPACE;
+        let minimum_balance = Rent::get()?.minimum_balance(space);
+        let system_program = self.system_program.to_account_info();
+
+        if escrow.lamports() == 0 {
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: self.sender.to_account_info(),
+                        to: escrow.clone(),
+                    },
+                    &[signer_seeds],
+                ),
+                minimum_balance,
+                space as u64,
+                &crate::ID,
+            )?;
+            return Ok(());
+        }
+
+        let lamports_diff = minimum_balance.saturating_sub(escrow.lamports());
+        if lamports_diff > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: self.sender.to_account_info(),
+                        to: escrow.clone(),
+                    },
+                ),
+                lamports_diff,
+            )?;
+        }
+        anchor_lang::system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: escrow.clone(),
+                },
+                &[signer_seeds],
+            ),
+            space as u64,
+        )?;
+        anchor_lang::system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: escrow.clone(),
+                },
+                &[signer_seeds],
+            ),
+            &crate::ID,
+        )?;
+        Ok(())
+    }
+
+    /// Create the escrow ATA, tolerating an existing one as `init_if_needed` would.
+    fn create_escrow_token_account(
+        &self,
+        escrow: &AccountInfo<'info>,
+        escrow_token: &AccountInfo<'info>,
+    ) -> Result<()> {
+        anchor_spl::associated_token::create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.sender.to_account_info(),
+                associated_token: escrow_token.clone(),
+                authority: escrow.clone(),
+                mint: self.token_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+}
+
+pub fn handle_create_vesting_escrow_batch<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowBatchCtx<'info>>,
+    params: &[CreateVestingEscrowParameters],
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        !params.is_empty() && remaining_accounts.len() == params.len() * 3,
+        LockerError::InvalidBatchAccounts
+    );
+
+    token2022::validate_mint(&ctx.accounts.token_mint)?;
+
+    let token_mint = ctx.accounts.token_mint.key();
+    let token_program = ctx.accounts.token_program.key();
+
+    for (index, (params, accounts)) in params
+        .iter()
+        .zip(remaining_accounts.chunks_exact(3))
+        .enumerate()
+    {
+        params.validate()?;
+
+        let recipient = &accounts[0];
+        let escrow_info = &accounts[1];
+        let escrow_token_info = &accounts[2];
+
+        let base = get_batch_base(&ctx.accounts.base.key(), index as u64);
+        let (escrow_key, escrow_bump) =
+            Pubkey::find_program_address(&[b"escrow".as_ref(), base.as_ref()], &crate::ID);
+        require!(
+            escrow_key == escrow_info.key(),
+            LockerError::InvalidEscrowAddress
+        );
+
+        let escrow_token_key =
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &escrow_key,
+                &token_mint,
+                &token_program,
+            );
+        require!(
+            escrow_token_key == escrow_token_info.key(),
+            LockerError::InvalidEscrowTokenAddress
+        );
+
+        ctx.accounts.create_escrow_account(
+            escrow_info,
+            &[b"escrow".as_ref(), base.as_ref(), &[escrow_bump]],
+        )?;
+
+        let mut escrow = VestingEscrow::default();
+        escrow.init(
+            params.vesting_start_time,
+            params.cliff_time,
+            params.frequency,
+            params.cliff_unlock_amount,
+            params.amount_per_period,
+            params.number_of_period,
+            recipient.key(),
+            token_mint,
+            ctx.accounts.sender.key(),
+            base,
+            escrow_bump,
+            params.update_recipient_mode,
+            params.cancel_mode,
+            params.schedule_kind,
+            params.time_unit,
+            params.allow_crank_claim,
+            params.recipient_change_delay,
+        );
+        escrow.try_serialize(&mut &mut escrow_info.try_borrow_mut_data()?[..])?;
+
+        ctx.accounts
+            .create_escrow_token_account(escrow_info, escrow_token_info)?;
+        let mut escrow_token = InterfaceAccount::<TokenAccount>::try_from(escrow_token_info)?;
+        // a stray balance would be swept to the creator without ever being accounted for
+        require!(escrow_token.amount == 0, LockerError::EscrowTokenNotEmpty);
+        transfer_deposit_to_escrow(
+            &ctx.accounts.token_program,
+            &ctx.accounts.token_mint,
+            &ctx.accounts.sender,
+            &ctx.accounts.sender_token,
+            &mut escrow_token,
+            params.get_total_deposit_amount()?,
+        )?;
+
+        emit_cpi!(EventCreateVestingEscrow {
+            cliff_time: params.cliff_time,
+            frequency: params.frequency,
+            cliff_unlock_amount: params.cliff_unlock_amount,
+            amount_per_period: params.amount_per_period,
+            number_of_period: params.number_of_period,
+            recipient: recipient.key(),
+            escrow: escrow_key,
+            update_recipient_mode: params.update_recipient_mode,
+            cancel_mode: params.cancel_mode,
+            schedule_kind: params.schedule_kind,
+            time_unit: params.time_unit,
+            allow_crank_claim: params.allow_crank_claim,
+            vault_kind: VaultKind::AssociatedToken.into(),
+            recipient_change_delay: params.recipient_change_delay,
+            vesting_start_time: params.vesting_start_time,
+        });
+    }
+    Ok(())
+}
@@ -42,6 +42,8 @@ pub fn handle_create_vesting_escrow_metadata(
     ctx: Context<CreateVestingEscrowMetadataCtx>,
     params: &CreateVestingEscrowMetadataParameters,
 ) -> Result<()> {
+    VestingEscrowMetadata::validate(params)?;
+
     let escrow_metadata = &mut ctx.accounts.escrow_metadata;
     escrow_metadata.escrow = ctx.accounts.escrow.key();
     escrow_metadata.name = params.name.clone();
@@ -0,0 +1,196 @@
+use crate::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use arbitrary::Arbitrary;
+
+/// Accounts for [locker::create_vesting_escrow_with_schedule].
+#[derive(AnchorSerialize, AnchorDeserialize, Arbitrary, Debug)]
+pub struct CreateVestingEscrowWithScheduleParameters {
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=1_000_000)
+    )]
+    pub vesting_start_time: u64,
+    pub points: Vec<SchedulePoint>,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=3)
+    )]
+    pub update_recipient_mode: u8,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=3)
+    )]
+    pub cancel_mode: u8,
+    #[arbitrary(
+        with = |u: &mut arbitrary::Unstructured| u.int_in_range(0..=1)
+    )]
+    pub time_unit: u8,
+    pub allow_crank_claim: bool,
+    pub recipient_change_delay: u64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(params: CreateVestingEscrowWithScheduleParameters)]
+pub struct CreateVestingEscrowWithScheduleCtx<'info> {
+    #[account(mut)]
+    pub base: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            b"escrow".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + VestingEscrow::INIT_SPACE
+    )]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    #[account(
+        init,
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + VestingSchedule::space(params.points.len())
+    )]
+    pub vesting_schedule: Box<Account<'info, VestingSchedule>>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow ATA, created here. An existing one is accepted only while it holds no tokens.
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient account
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_vesting_escrow_with_schedule(
+    ctx: Context<CreateVestingEscrowWithScheduleCtx>,
+    params: &CreateVestingEscrowWithScheduleParameters,
+) -> Result<()> {
+    let CreateVestingEscrowWithScheduleParameters {
+        vesting_start_time,
+        points,
+        update_recipient_mode,
+        cancel_mode,
+        time_unit,
+        allow_crank_claim,
+        recipient_change_delay,
+    } = params;
+
+    VestingSchedule::validate_points(points)?;
+
+    let cliff_time = points[0].timestamp;
+    require!(
+        cliff_time >= *vesting_start_time,
+        LockerError::InvalidVestingStartTime
+    );
+
+    require!(
+        UpdateRecipientMode::try_from(*update_recipient_mode).is_ok(),
+        LockerError::InvalidUpdateRecipientMode,
+    );
+
+    require!(
+        CancelMode::try_from(*cancel_mode).is_ok(),
+        LockerError::InvalidCancelMode,
+    );
+
+    require!(
+        TimeUnit::try_from(*time_unit).is_ok(),
+        LockerError::InvalidTimeUnit,
+    );
+
+    token2022::validate_mint(&ctx.accounts.token_mint)?;
+
+    let schedule_kind = ScheduleKind::Custom.into();
+    let escrow = &mut ctx.accounts.escrow;
+
+    // the unlock curve lives in the vesting schedule, the periodic fields stay empty
+    escrow.init(
+        *vesting_start_time,
+        cliff_time,
+        0,
+        0,
+        0,
+        0,
+        ctx.accounts.recipient.key(),
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.sender.key(),
+        ctx.accounts.base.key(),
+        ctx.bumps.escrow,
+        *update_recipient_mode,
+        *cancel_mode,
+        schedule_kind,
+        *time_unit,
+        *allow_crank_claim,
+        *recipient_change_delay,
+    );
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.escrow = ctx.accounts.escrow.key();
+    vesting_schedule.points = points.clone();
+
+    // a stray balance would be swept to the creator without ever being accounted for
+    require!(
+        ctx.accounts.escrow_token.amount == 0,
+        LockerError::EscrowTokenNotEmpty
+    );
+    transfer_deposit_to_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.sender,
+        &ctx.accounts.sender_token,
+        &mut ctx.accounts.escrow_token,
+        ctx.accounts.vesting_schedule.get_total_deposit_amount(),
+    )?;
+
+    emit_cpi!(EventCreateVestingEscrow {
+        cliff_time,
+        frequency: 0,
+        cliff_unlock_amount: 0,
+        amount_per_period: 0,
+        number_of_period: 0,
+        recipient: ctx.accounts.recipient.key(),
+        escrow: ctx.accounts.escrow.key(),
+        update_recipient_mode: *update_recipient_mode,
+        cancel_mode: *cancel_mode,
+        schedule_kind,
+        time_unit: *time_unit,
+        allow_crank_claim: *allow_crank_claim,
+        vault_kind: VaultKind::AssociatedToken.into(),
+        recipient_change_delay: *recipient_change_delay,
+        vesting_start_time: *vesting_start_time,
+    });
+    emit_cpi!(EventCreateVestingSchedule {
+        escrow: ctx.accounts.escrow.key(),
+        vesting_schedule: ctx.accounts.vesting_schedule.key(),
+        points: points.clone(),
+    });
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+/// Accounts for [locker::create_vesting_escrow_with_vault].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVestingEscrowWithVaultCtx<'info> {
+    #[account(mut)]
+    pub base: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            b"escrow".as_ref(),
+            base.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        space = 8 + VestingEscrow::INIT_SPACE
+    )]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Escrow vault, a token account owned by the escrow at a PDA of this program.
+    #[account(
+        init,
+        seeds = [
+            b"vault".as_ref(),
+            escrow.key().as_ref(),
+        ],
+        bump,
+        payer = sender,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub sender_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: recipient account
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // system program
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_vesting_escrow_with_vault(
+    ctx: Context<CreateVestingEscrowWithVaultCtx>,
+    params: &CreateVestingEscrowParameters,
+) -> Result<()> {
+    let &CreateVestingEscrowParameters {
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        cliff_unlock_amount,
+        amount_per_period,
+        number_of_period,
+        update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        recipient_change_delay,
+    } = params;
+
+    params.validate()?;
+
+    token2022::validate_mint(&ctx.accounts.token_mint)?;
+
+    let escrow = &mut ctx.accounts.escrow;
+
+    escrow.init(
+        vesting_start_time,
+        cliff_time,
+        frequency,
+        cliff_unlock_amount,
+        amount_per_period,
+        number_of_period,
+        ctx.accounts.recipient.key(),
+        ctx.accounts.token_mint.key(),
+        ctx.accounts.sender.key(),
+        ctx.accounts.base.key(),
+        ctx.bumps.escrow,
+        update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        recipient_change_delay,
+    );
+    escrow.set_pda_vault(ctx.bumps.escrow_token);
+
+    transfer_deposit_to_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.sender,
+        &ctx.accounts.sender_token,
+        &mut ctx.accounts.escrow_token,
+        params.get_total_deposit_amount()?,
+    )?;
+
+    emit_cpi!(EventCreateVestingEscrow {
+        cliff_time,
+        frequency,
+        cliff_unlock_amount,
+        amount_per_period,
+        number_of_period,
+        recipient: ctx.accounts.recipient.key(),
+        escrow: ctx.accounts.escrow.key(),
+        update_recipient_mode,
+        cancel_mode,
+        schedule_kind,
+        time_unit,
+        allow_crank_claim,
+        vault_kind: VaultKind::Pda.into(),
+        recipient_change_delay,
+        vesting_start_time,
+    });
+    Ok(())
+}
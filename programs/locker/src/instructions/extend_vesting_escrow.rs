@@ -0,0 +1,77 @@
+use crate::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct ExtendVestingEscrowParameters {
+    /// new number of periods, at least the current one
+    pub number_of_period: u64,
+    /// new amount per period, at least the current one
+    pub amount_per_period: u64,
+}
+
+/// Accounts for [locker::extend_vesting_escrow].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendVestingEscrowCtx<'info> {
+    /// Escrow.
+    #[account(mut, has_one = creator, has_one = token_mint)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+        constraint = escrow_token.key() == escrow.get_escrow_token_address(&escrow.key(), &token_program.key())? @ LockerError::InvalidEscrowTokenAddress
+    )]
+    pub escrow_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator of the escrow, funds the extension.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut, token::mint = token_mint, token::token_program = token_program)]
+    pub creator_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle_extend_vesting_escrow(
+    ctx: Context<ExtendVestingEscrowCtx>,
+    params: &ExtendVestingEscrowParameters,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    require!(!escrow.is_cancelled(), LockerError::AlreadyCancelled);
+
+    let old_number_of_period = escrow.number_of_period;
+    let old_amount_per_period = escrow.amount_per_period;
+    let deposit_amount = escrow.extend(
+        current_ts,
+        params.number_of_period,
+        params.amount_per_period,
+    )?;
+
+    transfer_deposit_to_escrow(
+        &ctx.accounts.token_program,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.creator,
+        &ctx.accounts.creator_token,
+        &mut ctx.accounts.escrow_token,
+        deposit_amount,
+    )?;
+
+    emit_cpi!(EventExtendVestingEscrow {
+        escrow: ctx.accounts.escrow.key(),
+        old_number_of_period,
+        old_amount_per_period,
+        number_of_period: params.number_of_period,
+        amount_per_period: params.amount_per_period,
+        deposit_amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Returned by [locker::get_vesting_status].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingStatus {
+    /// amount the recipient can claim now
+    pub claimable_amount: u64,
+    /// total amount unlocked so far, claimed or not
+    pub unlocked_amount: u64,
+    /// amount still to unlock
+    pub locked_amount: u64,
+    /// part of the locked amount already accrued and released at the cliff
+    pub accrued_locked_amount: u64,
+    /// next time more tokens unlock, none once the schedule has ended
+    pub next_unlock_time: Option<u64>,
+    /// time from which nothing more unlocks
+    pub vesting_end_time: u64,
+}
+
+/// Accounts for [locker::get_vesting_status].
+#[derive(Accounts)]
+pub struct GetVestingStatusCtx<'info> {
+    /// Escrow.
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Vesting schedule, required for escrows with a custom schedule.
+    #[account(
+        seeds = [
+            b"vesting_schedule".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow
+    )]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+}
+
+pub fn handle_get_vesting_status(ctx: Context<GetVestingStatusCtx>) -> Result<VestingStatus> {
+    let escrow = &ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    let vesting_schedule = ctx.accounts.vesting_schedule.as_deref();
+
+    Ok(VestingStatus {
+        claimable_amount: escrow
+            .get_claimable_amount_with_schedule(current_ts, vesting_schedule)?,
+        unlocked_amount: escrow
+            .get_max_unlocked_amount_with_schedule(current_ts, vesting_schedule)?,
+        locked_amount: escrow.get_locked_amount_with_schedule(current_ts, vesting_schedule)?,
+        accrued_locked_amount: escrow.get_accrued_locked_amount(current_ts)?,
+        next_unlock_time: escrow
+            .get_next_unlock_time_with_schedule(current_ts, vesting_schedule)?,
+        vesting_end_time: escrow.get_vesting_end_time_with_schedule(vesting_schedule)?,
+    })
+}
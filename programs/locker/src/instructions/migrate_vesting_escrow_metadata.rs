@@ -0,0 +1,48 @@
+use crate::*;
+
+/// Accounts for [locker::migrate_vesting_escrow_metadata].
+#[derive(Accounts)]
+pub struct MigrateVestingEscrowMetadataCtx<'info> {
+    /// The [Escrow].
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// CHECK: escrow metadata in the legacy layout, which [Account] cannot load; checked in the handler
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_metadata".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        owner = crate::ID
+    )]
+    pub escrow_metadata: UncheckedAccount<'info>,
+
+    /// Payer of the extra rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_migrate_vesting_escrow_metadata(
+    ctx: Context<MigrateVestingEscrowMetadataCtx>,
+) -> Result<()> {
+    let escrow_metadata_info = ctx.accounts.escrow_metadata.to_account_info();
+    require!(
+        VestingEscrowMetadata::is_legacy(&escrow_metadata_info.try_borrow_data()?),
+        LockerError::MetadataNotLegacy
+    );
+
+    let old_len = escrow_metadata_info.data_len();
+    realloc_escrow_metadata(
+        &escrow_metadata_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        old_len + VestingEscrowMetadata::LEGACY_MIGRATION_SPACE,
+    )?;
+    // empty `uri` and `attributes`
+    escrow_metadata_info.try_borrow_mut_data()?[old_len..].fill(0);
+    Ok(())
+}
@@ -1,11 +1,80 @@
 pub mod create_vesting_escrow;
 pub use create_vesting_escrow::*;
 
+pub mod create_vesting_escrow_batch;
+pub use create_vesting_escrow_batch::*;
+
+pub mod create_vesting_escrow_with_schedule;
+pub use create_vesting_escrow_with_schedule::*;
+
+pub mod create_vesting_escrow_with_vault;
+pub use create_vesting_escrow_with_vault::*;
+
+pub mod extend_vesting_escrow;
+pub use extend_vesting_escrow::*;
+
 pub mod claim;
 pub use claim::*;
 
+pub mod claim_as_delegate;
+pub use claim_as_delegate::*;
+
+pub mod set_claim_delegate;
+pub use set_claim_delegate::*;
+
+pub mod revoke_claim_delegate;
+pub use revoke_claim_delegate::*;
+
+pub mod crank_claim;
+pub use crank_claim::*;
+
+pub mod get_vesting_status;
+pub use get_vesting_status::*;
+
 pub mod create_vesting_escrow_metadata;
 pub use create_vesting_escrow_metadata::*;
 
+pub mod update_vesting_escrow_metadata;
+pub use update_vesting_escrow_metadata::*;
+
+pub mod set_vesting_escrow_metadata_uri;
+pub use set_vesting_escrow_metadata_uri::*;
+
+pub mod set_vesting_escrow_metadata_attribute;
+pub use set_vesting_escrow_metadata_attribute::*;
+
+pub mod remove_vesting_escrow_metadata_attribute;
+pub use remove_vesting_escrow_metadata_attribute::*;
+
+pub mod migrate_vesting_escrow_metadata;
+pub use migrate_vesting_escrow_metadata::*;
+
 pub mod update_vesting_escrow_recipient;
 pub use update_vesting_escrow_recipient::*;
+
+pub mod propose_vesting_escrow_recipient;
+pub use propose_vesting_escrow_recipient::*;
+
+pub mod accept_vesting_escrow_recipient;
+pub use accept_vesting_escrow_recipient::*;
+
+pub mod cancel_vesting_escrow_recipient_proposal;
+pub use cancel_vesting_escrow_recipient_proposal::*;
+
+pub mod veto_vesting_escrow_recipient;
+pub use veto_vesting_escrow_recipient::*;
+
+pub mod apply_vesting_escrow_recipient;
+pub use apply_vesting_escrow_recipient::*;
+
+pub mod close_vesting_escrow;
+pub use close_vesting_escrow::*;
+
+pub mod cancel_vesting_escrow;
+pub use cancel_vesting_escrow::*;
+
+pub mod create_merkle_vesting_distributor;
+pub use create_merkle_vesting_distributor::*;
+
+pub mod claim_merkle;
+pub use claim_merkle::*;
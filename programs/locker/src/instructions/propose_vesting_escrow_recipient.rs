@@ -0,0 +1,52 @@
+use crate::*;
+
+/// Accounts for [locker::propose_vesting_escrow_recipient].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct ProposeVestingEscrowRecipientCtx<'info> {
+    /// Escrow.
+    #[account(mut)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Signer.
+    pub signer: Signer<'info>,
+}
+
+pub fn handle_propose_vesting_escrow_recipient(
+    ctx: Context<ProposeVestingEscrowRecipientCtx>,
+    new_recipient: Pubkey,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+
+    // a due change decides who may propose, one still pending must be vetoed first
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        !escrow.has_scheduled_recipient(),
+        LockerError::RecipientChangeScheduled
+    );
+
+    let signer = ctx.accounts.signer.key();
+    escrow.validate_update_recipient_signer(signer)?;
+
+    require!(
+        new_recipient != Pubkey::default(),
+        LockerError::InvalidNewRecipient
+    );
+
+    escrow.propose_recipient(new_recipient);
+
+    emit_cpi!(EventProposeVestingEscrowRecipient {
+        escrow: ctx.accounts.escrow.key(),
+        recipient: ctx.accounts.escrow.recipient,
+        pending_recipient: new_recipient,
+        signer,
+    });
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+/// Accounts for [locker::remove_vesting_escrow_metadata_attribute].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct RemoveVestingEscrowMetadataAttributeCtx<'info> {
+    /// The [Escrow].
+    #[account(has_one = creator)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Escrow metadata.
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_metadata".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow @ LockerError::InvalidEscrowMetadata
+    )]
+    pub escrow_metadata: Box<Account<'info, VestingEscrowMetadata>>,
+
+    /// Creator of the escrow, refunded the freed rent.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_remove_vesting_escrow_metadata_attribute(
+    ctx: Context<RemoveVestingEscrowMetadataAttributeCtx>,
+    key: String,
+) -> Result<()> {
+    let escrow_metadata = &mut ctx.accounts.escrow_metadata;
+    escrow_metadata.remove_attribute(&key)?;
+
+    realloc_escrow_metadata(
+        &escrow_metadata.to_account_info(),
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        8 + escrow_metadata.get_space(),
+    )?;
+
+    emit_cpi!(EventRemoveVestingEscrowMetadataAttribute {
+        escrow: ctx.accounts.escrow.key(),
+        escrow_metadata: ctx.accounts.escrow_metadata.key(),
+        key,
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Accounts for [locker::revoke_claim_delegate].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct RevokeClaimDelegateCtx<'info> {
+    /// Escrow.
+    #[account(mut)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Recipient of the escrow, once any due recipient change is applied.
+    pub recipient: Signer<'info>,
+}
+
+pub fn handle_revoke_claim_delegate(ctx: Context<RevokeClaimDelegateCtx>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+
+    // a due change already revoked the old recipient's delegate
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        escrow.recipient == ctx.accounts.recipient.key(),
+        LockerError::NotPermitToDoThisAction
+    );
+    require!(
+        escrow.has_claim_delegate(),
+        LockerError::ClaimDelegateNotSet
+    );
+
+    let claim_delegate = escrow.claim_delegate;
+    escrow.revoke_claim_delegate();
+
+    emit_cpi!(EventRevokeClaimDelegate {
+        escrow: ctx.accounts.escrow.key(),
+        claim_delegate,
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Accounts for [locker::set_claim_delegate].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct SetClaimDelegateCtx<'info> {
+    /// Escrow.
+    #[account(mut)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Recipient of the escrow, once any due recipient change is applied.
+    pub recipient: Signer<'info>,
+}
+
+pub fn handle_set_claim_delegate(
+    ctx: Context<SetClaimDelegateCtx>,
+    claim_delegate: Pubkey,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+
+    // only the recipient after any due change may pick the delegate
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
+    require!(
+        escrow.recipient == ctx.accounts.recipient.key(),
+        LockerError::NotPermitToDoThisAction
+    );
+
+    let old_claim_delegate = escrow.claim_delegate;
+    escrow.set_claim_delegate(claim_delegate)?;
+
+    emit_cpi!(EventSetClaimDelegate {
+        escrow: ctx.accounts.escrow.key(),
+        old_claim_delegate,
+        claim_delegate,
+    });
+    Ok(())
+}
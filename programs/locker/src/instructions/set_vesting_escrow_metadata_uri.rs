@@ -0,0 +1,51 @@
+use crate::*;
+
+/// Accounts for [locker::set_vesting_escrow_metadata_uri].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct SetVestingEscrowMetadataUriCtx<'info> {
+    /// The [Escrow].
+    #[account(has_one = creator)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Escrow metadata.
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_metadata".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow @ LockerError::InvalidEscrowMetadata
+    )]
+    pub escrow_metadata: Box<Account<'info, VestingEscrowMetadata>>,
+
+    /// Creator of the escrow, pays for or is refunded the rent difference.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_set_vesting_escrow_metadata_uri(
+    ctx: Context<SetVestingEscrowMetadataUriCtx>,
+    uri: String,
+) -> Result<()> {
+    let escrow_metadata = &mut ctx.accounts.escrow_metadata;
+    escrow_metadata.set_uri(uri.clone())?;
+
+    realloc_escrow_metadata(
+        &escrow_metadata.to_account_info(),
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        8 + escrow_metadata.get_space(),
+    )?;
+
+    emit_cpi!(EventSetVestingEscrowMetadataUri {
+        escrow: ctx.accounts.escrow.key(),
+        escrow_metadata: ctx.accounts.escrow_metadata.key(),
+        uri,
+    });
+    Ok(())
+}
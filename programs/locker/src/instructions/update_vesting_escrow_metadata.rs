@@ -0,0 +1,125 @@
+use crate::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+/// Fields left as `None` keep their current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug)]
+pub struct UpdateVestingEscrowMetadataParameters {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub creator_email: Option<String>,
+    pub recipient_email: Option<String>,
+}
+
+/// Accounts for [locker::update_vesting_escrow_metadata].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct UpdateVestingEscrowMetadataCtx<'info> {
+    /// The [Escrow].
+    #[account(has_one = creator)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Escrow metadata.
+    #[account(
+        mut,
+        seeds = [
+            b"escrow_metadata".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        has_one = escrow @ LockerError::InvalidEscrowMetadata
+    )]
+    pub escrow_metadata: Box<Account<'info, VestingEscrowMetadata>>,
+
+    /// Creator of the escrow, pays for or is refunded the rent difference.
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Realloc the escrow metadata account to `new_len`, topping up its rent from `payer`
+/// when it grows and refunding the surplus to `payer` when it shrinks.
+pub fn realloc_escrow_metadata<'info>(
+    escrow_metadata_info: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    require!(
+        new_len <= escrow_metadata_info.data_len() + MAX_PERMITTED_DATA_INCREASE,
+        LockerError::MetadataReallocTooLarge
+    );
+
+    let new_minimum_balance = Rent::get()?.minimum_balance(new_len);
+    let lamports = escrow_metadata_info.lamports();
+
+    if new_minimum_balance > lamports {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: payer.to_account_info(),
+                to: escrow_metadata_info.clone(),
+            },
+        );
+        anchor_lang::system_program::transfer(cpi_context, new_minimum_balance - lamports)?;
+    } else if lamports > new_minimum_balance {
+        // the program owns the metadata account, so it can debit it directly
+        let surplus = lamports - new_minimum_balance;
+        escrow_metadata_info.sub_lamports(surplus)?;
+        payer.add_lamports(surplus)?;
+    }
+
+    escrow_metadata_info.realloc(new_len, false)?;
+    Ok(())
+}
+
+pub fn handle_update_vesting_escrow_metadata(
+    ctx: Context<UpdateVestingEscrowMetadataCtx>,
+    params: UpdateVestingEscrowMetadataParameters,
+) -> Result<()> {
+    // the email has to keep matching the recipient until a scheduled change applies
+    require!(
+        params.recipient_email.is_none() || !ctx.accounts.escrow.has_scheduled_recipient(),
+        LockerError::RecipientEmailWithScheduledChange
+    );
+
+    let escrow_metadata = &ctx.accounts.escrow_metadata;
+    let metadata = CreateVestingEscrowMetadataParameters {
+        name: params.name.unwrap_or_else(|| escrow_metadata.name.clone()),
+        description: params
+            .description
+            .unwrap_or_else(|| escrow_metadata.description.clone()),
+        creator_email: params
+            .creator_email
+            .unwrap_or_else(|| escrow_metadata.creator_email.clone()),
+        recipient_email: params
+            .recipient_email
+            .unwrap_or_else(|| escrow_metadata.recipient_email.clone()),
+    };
+
+    VestingEscrowMetadata::validate(&metadata)?;
+
+    let escrow_metadata = &mut ctx.accounts.escrow_metadata;
+    escrow_metadata.name = metadata.name.clone();
+    escrow_metadata.description = metadata.description.clone();
+    escrow_metadata.creator_email = metadata.creator_email.clone();
+    escrow_metadata.recipient_email = metadata.recipient_email.clone();
+
+    realloc_escrow_metadata(
+        &escrow_metadata.to_account_info(),
+        &ctx.accounts.creator,
+        &ctx.accounts.system_program,
+        8 + escrow_metadata.get_space(),
+    )?;
+
+    emit_cpi!(EventUpdateVestingEscrowMetadata {
+        escrow: ctx.accounts.escrow.key(),
+        escrow_metadata: ctx.accounts.escrow_metadata.key(),
+        name: metadata.name,
+        description: metadata.description,
+        creator_email: metadata.creator_email,
+        recipient_email: metadata.recipient_email,
+    });
+    Ok(())
+}
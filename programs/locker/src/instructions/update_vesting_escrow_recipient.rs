@@ -1,6 +1,9 @@
 use crate::*;
 
 /// Accounts for [locker::update_vesting_escrow_recipient].
+///
+/// Deprecated in favour of [locker::propose_vesting_escrow_recipient], and rejected on
+/// escrows that have used it, since a mistyped key here loses the whole stream.
 #[derive(Accounts)]
 #[event_cpi]
 pub struct UpdateVestingEscrowRecipientCtx<'info> {
@@ -25,74 +28,64 @@ pub fn handle_update_vesting_escrow_recipient(
     new_recipient_email: Option<String>,
 ) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    // a due change is applied first, so it is neither validated against nor replaced as stale
+    if let Some(old_recipient) = escrow.apply_scheduled_recipient(current_ts) {
+        emit_cpi!(EventApplyVestingEscrowRecipient {
+            escrow: escrow.key(),
+            old_recipient,
+            new_recipient: escrow.recipient,
+        });
+    }
     let old_recipient = escrow.recipient;
     let signer = ctx.accounts.signer.key();
-    let update_recipient_mode =
-        UpdateRecipientMode::try_from(escrow.update_recipient_mode).unwrap();
-
-    match update_recipient_mode {
-        UpdateRecipientMode::NeitherCreatorOrRecipient => {
-            return Err(LockerError::NotPermitToDoThisAction.into());
-        }
-        UpdateRecipientMode::OnlyCreator => {
-            require!(
-                signer == escrow.creator,
-                LockerError::NotPermitToDoThisAction
-            );
-        }
-        UpdateRecipientMode::OnlyRecipient => {
-            require!(
-                signer == escrow.recipient,
-                LockerError::NotPermitToDoThisAction
-            );
-        }
-        UpdateRecipientMode::EitherCreatorAndRecipient => {
-            require!(
-                signer == escrow.creator || signer == escrow.recipient,
-                LockerError::NotPermitToDoThisAction
-            );
-        }
-    }
+    escrow.validate_update_recipient_signer(signer)?;
+    require!(
+        !escrow.recipient_acceptance_required,
+        LockerError::RecipientAcceptanceRequired
+    );
 
-    escrow.update_recipient(new_recipient);
+    let effective_at = escrow.change_recipient(new_recipient, current_ts)?;
+    let is_scheduled = escrow.has_scheduled_recipient();
 
     if let Some(recipient_email) = new_recipient_email {
+        // the email has to keep matching the recipient until a scheduled change applies
+        require!(
+            !is_scheduled,
+            LockerError::RecipientEmailWithScheduledChange
+        );
         if let Some(escrow_metadata) = &mut ctx.accounts.escrow_metadata {
             require!(
                 escrow_metadata.escrow == ctx.accounts.escrow.key(),
                 LockerError::InvalidEscrowMetadata
             );
-            let escrow_metadata_info = escrow_metadata.to_account_info();
-            let new_len =
-                8 + VestingEscrowMetadata::space(&CreateVestingEscrowMetadataParameters {
-                    name: escrow_metadata.name.clone(),
-                    description: escrow_metadata.description.clone(),
-                    creator_email: escrow_metadata.creator_email.clone(),
-                    recipient_email: recipient_email.clone(),
-                });
-
-            // update rent fee
-            let rent = Rent::get()?;
-            let new_minimum_balance = rent.minimum_balance(new_len);
-            let lamports_diff = new_minimum_balance.saturating_sub(escrow_metadata_info.lamports());
+            VestingEscrowMetadata::validate_recipient_email(&recipient_email)?;
 
-            let cpi_context = CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.signer.to_account_info(),
-                    to: escrow_metadata_info.clone(),
-                },
-            );
-            anchor_lang::system_program::transfer(cpi_context, lamports_diff)?;
-
-            // realloc
-            escrow_metadata_info.realloc(new_len, false)?;
             // update new recipient_email
             escrow_metadata.recipient_email = recipient_email;
+
+            // realloc, settling the rent difference with the signer
+            let new_len = 8 + escrow_metadata.get_space();
+            realloc_escrow_metadata(
+                &escrow_metadata.to_account_info(),
+                &ctx.accounts.signer,
+                &ctx.accounts.system_program,
+                new_len,
+            )?;
         } else {
             return Err(LockerError::InvalidEscrowMetadata.into());
         }
     }
+    if is_scheduled {
+        emit_cpi!(EventScheduleVestingEscrowRecipient {
+            escrow: ctx.accounts.escrow.key(),
+            recipient: old_recipient,
+            scheduled_recipient: new_recipient,
+            effective_at,
+            signer,
+        });
+        return Ok(());
+    }
     emit_cpi!(EventUpdateVestingEscrowRecipient {
         escrow: ctx.accounts.escrow.key(),
         signer,
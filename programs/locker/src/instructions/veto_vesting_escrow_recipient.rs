@@ -0,0 +1,41 @@
+use crate::*;
+
+/// Accounts for [locker::veto_vesting_escrow_recipient].
+#[derive(Accounts)]
+#[event_cpi]
+pub struct VetoVestingEscrowRecipientCtx<'info> {
+    /// Escrow.
+    #[account(mut, has_one = creator)]
+    pub escrow: Account<'info, VestingEscrow>,
+
+    /// Creator of the escrow.
+    pub creator: Signer<'info>,
+}
+
+pub fn handle_veto_vesting_escrow_recipient(
+    ctx: Context<VetoVestingEscrowRecipientCtx>,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let current_ts = escrow.get_current_time(&Clock::get()?)?;
+    require!(
+        escrow.has_scheduled_recipient(),
+        LockerError::NoScheduledRecipient
+    );
+
+    let scheduled_recipient = escrow.pending_recipient;
+    let effective_at = escrow.recipient_change_effective_at;
+    require!(
+        current_ts < effective_at,
+        LockerError::RecipientChangeAlreadyEffective
+    );
+
+    escrow.cancel_pending_recipient();
+
+    emit_cpi!(EventVetoVestingEscrowRecipient {
+        escrow: ctx.accounts.escrow.key(),
+        scheduled_recipient,
+        effective_at,
+        signer: ctx.accounts.creator.key(),
+    });
+    Ok(())
+}
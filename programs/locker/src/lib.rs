@@ -14,6 +14,12 @@ pub use errors::*;
 
 pub mod safe_math;
 
+pub mod token2022;
+
+pub mod merkle;
+
+pub mod calendar;
+
 pub mod events;
 pub use events::*;
 
@@ -37,10 +43,77 @@ pub mod locker {
         handle_create_vesting_escrow(ctx, &params)
     }
 
+    pub fn create_vesting_escrow_with_schedule(
+        ctx: Context<CreateVestingEscrowWithScheduleCtx>,
+        params: CreateVestingEscrowWithScheduleParameters,
+    ) -> Result<()> {
+        handle_create_vesting_escrow_with_schedule(ctx, &params)
+    }
+
+    pub fn create_vesting_escrow_with_vault(
+        ctx: Context<CreateVestingEscrowWithVaultCtx>,
+        params: CreateVestingEscrowParameters,
+    ) -> Result<()> {
+        handle_create_vesting_escrow_with_vault(ctx, &params)
+    }
+
+    pub fn create_vesting_escrow_batch<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CreateVestingEscrowBatchCtx<'info>>,
+        params: Vec<CreateVestingEscrowParameters>,
+    ) -> Result<()> {
+        handle_create_vesting_escrow_batch(ctx, &params)
+    }
+
+    pub fn extend_vesting_escrow(
+        ctx: Context<ExtendVestingEscrowCtx>,
+        params: ExtendVestingEscrowParameters,
+    ) -> Result<()> {
+        handle_extend_vesting_escrow(ctx, &params)
+    }
+
     pub fn claim(ctx: Context<ClaimCtx>, max_amount: u64) -> Result<()> {
         handle_claim(ctx, max_amount)
     }
 
+    pub fn claim_as_delegate(ctx: Context<ClaimAsDelegateCtx>, max_amount: u64) -> Result<()> {
+        handle_claim_as_delegate(ctx, max_amount)
+    }
+
+    pub fn set_claim_delegate(
+        ctx: Context<SetClaimDelegateCtx>,
+        claim_delegate: Pubkey,
+    ) -> Result<()> {
+        handle_set_claim_delegate(ctx, claim_delegate)
+    }
+
+    pub fn revoke_claim_delegate(ctx: Context<RevokeClaimDelegateCtx>) -> Result<()> {
+        handle_revoke_claim_delegate(ctx)
+    }
+
+    pub fn crank_claim(ctx: Context<CrankClaimCtx>) -> Result<()> {
+        handle_crank_claim(ctx)
+    }
+
+    pub fn get_vesting_status(ctx: Context<GetVestingStatusCtx>) -> Result<VestingStatus> {
+        handle_get_vesting_status(ctx)
+    }
+
+    pub fn create_merkle_vesting_distributor(
+        ctx: Context<CreateMerkleVestingDistributorCtx>,
+        params: CreateMerkleVestingDistributorParameters,
+    ) -> Result<()> {
+        handle_create_merkle_vesting_distributor(ctx, &params)
+    }
+
+    pub fn claim_merkle(
+        ctx: Context<ClaimMerkleCtx>,
+        total_amount: u64,
+        proof: Vec<[u8; 32]>,
+        max_amount: u64,
+    ) -> Result<()> {
+        handle_claim_merkle(ctx, total_amount, proof, max_amount)
+    }
+
     pub fn create_vesting_escrow_metadata(
         ctx: Context<CreateVestingEscrowMetadataCtx>,
         params: CreateVestingEscrowMetadataParameters,
@@ -48,6 +121,41 @@ pub mod locker {
         handle_create_vesting_escrow_metadata(ctx, &params)
     }
 
+    pub fn update_vesting_escrow_metadata(
+        ctx: Context<UpdateVestingEscrowMetadataCtx>,
+        params: UpdateVestingEscrowMetadataParameters,
+    ) -> Result<()> {
+        handle_update_vesting_escrow_metadata(ctx, params)
+    }
+
+    pub fn set_vesting_escrow_metadata_uri(
+        ctx: Context<SetVestingEscrowMetadataUriCtx>,
+        uri: String,
+    ) -> Result<()> {
+        handle_set_vesting_escrow_metadata_uri(ctx, uri)
+    }
+
+    pub fn set_vesting_escrow_metadata_attribute(
+        ctx: Context<SetVestingEscrowMetadataAttributeCtx>,
+        key: String,
+        value: String,
+    ) -> Result<()> {
+        handle_set_vesting_escrow_metadata_attribute(ctx, key, value)
+    }
+
+    pub fn remove_vesting_escrow_metadata_attribute(
+        ctx: Context<RemoveVestingEscrowMetadataAttributeCtx>,
+        key: String,
+    ) -> Result<()> {
+        handle_remove_vesting_escrow_metadata_attribute(ctx, key)
+    }
+
+    pub fn migrate_vesting_escrow_metadata(
+        ctx: Context<MigrateVestingEscrowMetadataCtx>,
+    ) -> Result<()> {
+        handle_migrate_vesting_escrow_metadata(ctx)
+    }
+
     pub fn update_vesting_escrow_recipient(
         ctx: Context<UpdateVestingEscrowRecipientCtx>,
         new_recipient: Pubkey,
@@ -56,5 +164,42 @@ pub mod locker {
         handle_update_vesting_escrow_recipient(ctx, new_recipient, new_recipient_email)
     }
 
-    // TODO add function to close escrow after all token has been claimed
+    pub fn propose_vesting_escrow_recipient(
+        ctx: Context<ProposeVestingEscrowRecipientCtx>,
+        new_recipient: Pubkey,
+    ) -> Result<()> {
+        handle_propose_vesting_escrow_recipient(ctx, new_recipient)
+    }
+
+    pub fn accept_vesting_escrow_recipient(
+        ctx: Context<AcceptVestingEscrowRecipientCtx>,
+    ) -> Result<()> {
+        handle_accept_vesting_escrow_recipient(ctx)
+    }
+
+    pub fn cancel_vesting_escrow_recipient_proposal(
+        ctx: Context<CancelVestingEscrowRecipientProposalCtx>,
+    ) -> Result<()> {
+        handle_cancel_vesting_escrow_recipient_proposal(ctx)
+    }
+
+    pub fn veto_vesting_escrow_recipient(
+        ctx: Context<VetoVestingEscrowRecipientCtx>,
+    ) -> Result<()> {
+        handle_veto_vesting_escrow_recipient(ctx)
+    }
+
+    pub fn apply_vesting_escrow_recipient(
+        ctx: Context<ApplyVestingEscrowRecipientCtx>,
+    ) -> Result<()> {
+        handle_apply_vesting_escrow_recipient(ctx)
+    }
+
+    pub fn cancel_vesting_escrow(ctx: Context<CancelVestingEscrowCtx>) -> Result<()> {
+        handle_cancel_vesting_escrow(ctx)
+    }
+
+    pub fn close_vesting_escrow(ctx: Context<CloseVestingEscrowCtx>) -> Result<()> {
+        handle_close_vesting_escrow(ctx)
+    }
 }
@@ -9,3 +9,13 @@ macro_rules! escrow_seeds {
         ]
     };
 }
+
+macro_rules! merkle_distributor_seeds {
+    ($distributor:expr) => {
+        &[
+            b"merkle_distributor".as_ref(),
+            $distributor.base.as_ref(),
+            &[$distributor.distributor_bump],
+        ]
+    };
+}
@@ -0,0 +1,25 @@
+//! Merkle proof helpers shared with the off-chain tree builder
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+/// Prefixes keep a leaf from being passed off as an intermediate node.
+const LEAF_PREFIX: &[u8] = &[0];
+const INTERMEDIATE_PREFIX: &[u8] = &[1];
+
+pub fn hash_leaf(recipient: &Pubkey, total_amount: u64) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, recipient.as_ref(), &total_amount.to_le_bytes()]).to_bytes()
+}
+
+/// Children are sorted so a proof does not need to record left/right positions.
+pub fn hash_intermediate(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[INTERMEDIATE_PREFIX, left, right]).to_bytes()
+}
+
+pub fn verify_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_intermediate(&node, sibling));
+    computed_root == *root
+}
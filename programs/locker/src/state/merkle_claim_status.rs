@@ -0,0 +1,24 @@
+use crate::*;
+
+use self::safe_math::SafeMath;
+
+/// Claim progress of one recipient of a [MerkleVestingDistributor].
+#[account]
+#[derive(Default, InitSpace, Debug)]
+pub struct MerkleClaimStatus {
+    /// The [MerkleVestingDistributor].
+    pub distributor: Pubkey,
+    /// recipient address
+    pub recipient: Pubkey,
+    /// total amount of the recipient's leaf
+    pub total_amount: u64,
+    /// total claimed amount
+    pub total_claimed_amount: u64,
+}
+
+impl MerkleClaimStatus {
+    pub fn accumulate_claimed_amount(&mut self, claimed_amount: u64) -> Result<()> {
+        self.total_claimed_amount = self.total_claimed_amount.safe_add(claimed_amount)?;
+        Ok(())
+    }
+}
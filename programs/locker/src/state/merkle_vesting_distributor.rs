@@ -0,0 +1,134 @@
+use crate::*;
+
+use self::safe_math::SafeMath;
+use static_assertions::const_assert_eq;
+
+/// Denominator of [MerkleVestingDistributor::cliff_unlock_bps].
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// Vests tokens to every `(recipient, total_amount)` leaf of a merkle root on one shared schedule.
+#[account]
+#[derive(Default, InitSpace, Debug)]
+pub struct MerkleVestingDistributor {
+    /// merkle root of `(recipient, total_amount)` leaves
+    pub root: [u8; 32],
+    /// token mint
+    pub token_mint: Pubkey,
+    /// creator of the distributor
+    pub creator: Pubkey,
+    /// distributor base key
+    pub base: Pubkey,
+    /// distributor bump
+    pub distributor_bump: u8,
+    /// schedule_kind
+    pub schedule_kind: u8,
+    /// share of each total amount unlocked at the cliff, in basis points
+    pub cliff_unlock_bps: u16,
+    /// padding
+    pub padding_0: [u8; 4],
+    /// vesting start time
+    pub vesting_start_time: u64,
+    /// cliff time
+    pub cliff_time: u64,
+    /// frequency
+    pub frequency: u64,
+    /// number of period
+    pub number_of_period: u64,
+    /// total deposited amount
+    pub total_amount: u64,
+    /// total claimed amount
+    pub total_claimed_amount: u64,
+    /// buffer
+    pub buffer: [u128; 4],
+}
+
+const_assert_eq!(MerkleVestingDistributor::INIT_SPACE, 248); //  32 * 4 + 8 + 8 * 6 + 16 * 4
+
+impl MerkleVestingDistributor {
+    /// The [VestingEscrow] a recipient of `total_amount` would hold, so claims follow
+    /// exactly the same unlock math as escrows.
+    pub fn get_vesting_escrow(
+        &self,
+        recipient: Pubkey,
+        total_amount: u64,
+        total_claimed_amount: u64,
+    ) -> Result<VestingEscrow> {
+        let cliff_vested_amount = u128::from(total_amount)
+            .safe_mul(self.cliff_unlock_bps.into())?
+            .safe_div(MAX_BASIS_POINTS.into())?;
+        let cliff_vested_amount =
+            u64::try_from(cliff_vested_amount).map_err(|_| LockerError::MathOverflow)?;
+
+        let amount_per_period = if self.number_of_period == 0 {
+            0
+        } else {
+            total_amount
+                .safe_sub(cliff_vested_amount)?
+                .safe_div(self.number_of_period)?
+        };
+        // rounding dust is released at the cliff
+        let cliff_unlock_amount =
+            total_amount.safe_sub(amount_per_period.safe_mul(self.number_of_period)?)?;
+
+        Ok(VestingEscrow {
+            recipient,
+            token_mint: self.token_mint,
+            creator: self.creator,
+            base: self.base,
+            escrow_bump: self.distributor_bump,
+            schedule_kind: self.schedule_kind,
+            cliff_time: self.cliff_time,
+            frequency: self.frequency,
+            cliff_unlock_amount,
+            amount_per_period,
+            number_of_period: self.number_of_period,
+            total_claimed_amount,
+            vesting_start_time: self.vesting_start_time,
+            ..Default::default()
+        })
+    }
+
+    pub fn accumulate_claimed_amount(&mut self, claimed_amount: u64) -> Result<()> {
+        self.total_claimed_amount = self.total_claimed_amount.safe_add(claimed_amount)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod distributor_test {
+    use super::*;
+    use proptest::proptest;
+
+    proptest! {
+    #[test]
+    fn test_get_vesting_escrow(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_bps in 0..=MAX_BASIS_POINTS,
+        total_amount in 0..u64::MAX / 100,
+    ) {
+        let distributor = MerkleVestingDistributor {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_bps,
+            ..Default::default()
+        };
+
+        let escrow = distributor
+            .get_vesting_escrow(Pubkey::default(), total_amount, 0)
+            .unwrap();
+        assert_eq!(escrow.get_total_deposit_amount().unwrap(), total_amount);
+        assert!(
+            escrow.cliff_unlock_amount as u128
+                >= total_amount as u128 * cliff_unlock_bps as u128 / MAX_BASIS_POINTS as u128
+        );
+
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(cliff_time + frequency * number_of_period)
+            .unwrap();
+        assert_eq!(unlocked_amount, total_amount);
+        }
+    }
+}
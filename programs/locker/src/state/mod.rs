@@ -3,3 +3,12 @@ pub use vesting_escrow::*;
 
 pub mod vesting_escrow_metadata;
 pub use vesting_escrow_metadata::*;
+
+pub mod vesting_schedule;
+pub use vesting_schedule::*;
+
+pub mod merkle_vesting_distributor;
+pub use merkle_vesting_distributor::*;
+
+pub mod merkle_claim_status;
+pub use merkle_claim_status::*;
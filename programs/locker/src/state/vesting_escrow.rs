@@ -13,6 +13,39 @@ pub enum UpdateRecipientMode {
     EitherCreatorAndRecipient, //3
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum ScheduleKind {
+    Periodic,          //0
+    Linear,            //1
+    Custom,            //2
+    PeriodicFromStart, //3
+    CalendarMonthly,   //4
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum TimeUnit {
+    UnixTimestamp, //0
+    Slot,          //1
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum VaultKind {
+    AssociatedToken, //0
+    Pda,             //1
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum CancelMode {
+    NeitherCreatorOrRecipient, //0
+    OnlyCreator,               //1
+    OnlyRecipient,             //2
+    EitherCreatorAndRecipient, //3
+}
+
 #[account]
 #[derive(Default, InitSpace, Debug)]
 pub struct VestingEscrow {
@@ -28,11 +61,21 @@ pub struct VestingEscrow {
     pub escrow_bump: u8,
     /// update_recipient_mode
     pub update_recipient_mode: u8,
-    /// padding
-    pub padding_0: [u8; 6],
+    /// cancel_mode
+    pub cancel_mode: u8,
+    /// schedule_kind
+    pub schedule_kind: u8,
+    /// time_unit of every time field, unix timestamps or slots
+    pub time_unit: u8,
+    /// whether anyone can claim to the recipient's associated token account
+    pub allow_crank_claim: bool,
+    /// vault_kind of the escrow token account
+    pub vault_kind: u8,
+    /// bump of the `[b"vault", escrow]` token account, 0 for associated token accounts
+    pub vault_bump: u8,
     /// cliff time
     pub cliff_time: u64,
-    /// frequency
+    /// frequency, in seconds or in calendar months for calendar schedules
     pub frequency: u64,
     /// cliff unlock amount
     pub cliff_unlock_amount: u64,
@@ -44,13 +87,28 @@ pub struct VestingEscrow {
     pub total_claimed_amount: u64,
     /// vesting start time
     pub vesting_start_time: u64,
-    /// buffer
-    pub buffer: [u128; 6],
+    /// cancelled at, only meaningful once `cancelled` is set
+    pub cancelled_at: u64,
+    /// delay before a recipient change takes effect, 0 to apply changes immediately
+    pub recipient_change_delay: u64,
+    /// proposed or scheduled recipient, default if no change is pending
+    pub pending_recipient: Pubkey,
+    /// time the scheduled recipient takes over, 0 while a proposal awaits acceptance
+    pub recipient_change_effective_at: u64,
+    /// whether the escrow is cancelled, kept apart from `cancelled_at` since time 0 is a valid slot
+    pub cancelled: bool,
+    /// set by the first proposal, recipient changes then need the new recipient's acceptance
+    pub recipient_acceptance_required: bool,
+    /// padding
+    pub padding_1: [u8; 6],
+    /// key allowed to claim to the recipient's token account, default if none
+    pub claim_delegate: Pubkey,
 }
 
-const_assert_eq!(VestingEscrow::INIT_SPACE, 288); //  32 * 4 + 8 * 8 + 16 * 6
+const_assert_eq!(VestingEscrow::INIT_SPACE, 288); //  32 * 4 + 8 * 8 + 8 * 2 + 32 + 8 * 2 + 32
 
 impl VestingEscrow {
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
         vesting_start_time: u64,
@@ -65,6 +123,11 @@ impl VestingEscrow {
         base: Pubkey,
         escrow_bump: u8,
         update_recipient_mode: u8,
+        cancel_mode: u8,
+        schedule_kind: u8,
+        time_unit: u8,
+        allow_crank_claim: bool,
+        recipient_change_delay: u64,
     ) {
         self.vesting_start_time = vesting_start_time;
         self.cliff_time = cliff_time;
@@ -78,12 +141,126 @@ impl VestingEscrow {
         self.base = base;
         self.escrow_bump = escrow_bump;
         self.update_recipient_mode = update_recipient_mode;
+        self.cancel_mode = cancel_mode;
+        self.schedule_kind = schedule_kind;
+        self.time_unit = time_unit;
+        self.allow_crank_claim = allow_crank_claim;
+        self.recipient_change_delay = recipient_change_delay;
+    }
+
+    /// Current time in the escrow's [TimeUnit], which every schedule method takes as `current_ts`.
+    /// A negative unix timestamp is rejected rather than wrapped into the far future.
+    pub fn get_current_time(&self, clock: &Clock) -> Result<u64> {
+        if self.time_unit == u8::from(TimeUnit::Slot) {
+            return Ok(clock.slot);
+        }
+        let current_ts =
+            u64::try_from(clock.unix_timestamp).map_err(|_| LockerError::InvalidClock)?;
+        Ok(current_ts)
+    }
+
+    /// Hold the tokens in the `[b"vault", escrow]` token account instead of the escrow ATA.
+    pub fn set_pda_vault(&mut self, vault_bump: u8) {
+        self.vault_kind = VaultKind::Pda.into();
+        self.vault_bump = vault_bump;
+    }
+
+    /// Token account holding the escrow's tokens. PDA vaults use the stored bump, which is
+    /// far cheaper than the bump search an ATA derivation does.
+    pub fn get_escrow_token_address(
+        &self,
+        escrow: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Result<Pubkey> {
+        if self.vault_kind == u8::from(VaultKind::Pda) {
+            let escrow_token = Pubkey::create_program_address(
+                &[b"vault".as_ref(), escrow.as_ref(), &[self.vault_bump]],
+                &crate::ID,
+            )
+            .map_err(|_| LockerError::InvalidEscrowTokenAddress)?;
+            return Ok(escrow_token);
+        }
+        Ok(
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                escrow,
+                &self.token_mint,
+                token_program,
+            ),
+        )
     }
 
     pub fn get_max_unlocked_amount(&self, current_ts: u64) -> Result<u64> {
+        // a cancelled escrow stops unlocking at the cancellation time
+        let current_ts = if self.is_cancelled() {
+            current_ts.min(self.cancelled_at)
+        } else {
+            current_ts
+        };
         if current_ts < self.cliff_time {
             return Ok(0);
         }
+        let schedule_kind = ScheduleKind::try_from(self.schedule_kind)
+            .map_err(|_| LockerError::InvalidScheduleKind)?;
+
+        match schedule_kind {
+            ScheduleKind::Periodic => self.get_periodic_unlocked_amount(current_ts),
+            ScheduleKind::Linear => self.get_linear_unlocked_amount(current_ts),
+            ScheduleKind::Custom => Err(LockerError::MissingVestingSchedule.into()),
+            ScheduleKind::PeriodicFromStart => {
+                let accrued_amount = self.get_accrued_amount(current_ts)?;
+                Ok(self.cliff_unlock_amount.safe_add(accrued_amount)?)
+            }
+            ScheduleKind::CalendarMonthly => self.get_calendar_unlocked_amount(current_ts),
+        }
+    }
+
+    /// Amount accrued by `current_ts` on a [ScheduleKind::PeriodicFromStart] schedule but
+    /// still locked because the cliff has not passed, 0 for other schedule kinds.
+    pub fn get_accrued_locked_amount(&self, current_ts: u64) -> Result<u64> {
+        let current_ts = if self.is_cancelled() {
+            current_ts.min(self.cancelled_at)
+        } else {
+            current_ts
+        };
+        if current_ts >= self.cliff_time
+            || self.schedule_kind != u8::from(ScheduleKind::PeriodicFromStart)
+        {
+            return Ok(0);
+        }
+        self.get_accrued_amount(current_ts)
+    }
+
+    /// Periodic amounts accrued by `current_ts`, with periods counted from `vesting_start_time`.
+    fn get_accrued_amount(&self, current_ts: u64) -> Result<u64> {
+        let period = current_ts
+            .saturating_sub(self.vesting_start_time)
+            .safe_div(self.frequency)?;
+        let period = period.min(self.number_of_period);
+
+        let accrued_amount = period.safe_mul(self.amount_per_period)?;
+
+        Ok(accrued_amount)
+    }
+
+    /// Like [Self::get_max_unlocked_amount], reading custom schedules from `vesting_schedule`.
+    pub fn get_max_unlocked_amount_with_schedule(
+        &self,
+        current_ts: u64,
+        vesting_schedule: Option<&VestingSchedule>,
+    ) -> Result<u64> {
+        if self.schedule_kind != u8::from(ScheduleKind::Custom) {
+            return self.get_max_unlocked_amount(current_ts);
+        }
+        let vesting_schedule = vesting_schedule.ok_or(LockerError::MissingVestingSchedule)?;
+        let current_ts = if self.is_cancelled() {
+            current_ts.min(self.cancelled_at)
+        } else {
+            current_ts
+        };
+        Ok(vesting_schedule.get_max_unlocked_amount(current_ts))
+    }
+
+    fn get_periodic_unlocked_amount(&self, current_ts: u64) -> Result<u64> {
         let period = current_ts
             .safe_sub(self.cliff_time)?
             .safe_div(self.frequency)?;
@@ -96,26 +273,394 @@ impl VestingEscrow {
         Ok(unlocked_amount)
     }
 
+    /// Periods of `frequency` UTC calendar months counted from the cliff.
+    fn get_calendar_unlocked_amount(&self, current_ts: u64) -> Result<u64> {
+        let period =
+            calendar::months_between(self.cliff_time, current_ts).safe_div(self.frequency)?;
+        let period = period.min(self.number_of_period);
+
+        let unlocked_amount = self
+            .cliff_unlock_amount
+            .safe_add(period.safe_mul(self.amount_per_period)?)?;
+
+        Ok(unlocked_amount)
+    }
+
+    /// Release `amount_per_period * number_of_period` per second over
+    /// `frequency * number_of_period` seconds after the cliff, rounded down.
+    fn get_linear_unlocked_amount(&self, current_ts: u64) -> Result<u64> {
+        let total_duration = u128::from(self.frequency).safe_mul(self.number_of_period.into())?;
+        if total_duration == 0 {
+            return Ok(self.cliff_unlock_amount);
+        }
+        let total_linear_amount =
+            u128::from(self.amount_per_period).safe_mul(self.number_of_period.into())?;
+
+        let elapsed = u128::from(current_ts.safe_sub(self.cliff_time)?).min(total_duration);
+        let linear_unlocked_amount = total_linear_amount
+            .safe_mul(elapsed)?
+            .safe_div(total_duration)?;
+        let linear_unlocked_amount =
+            u64::try_from(linear_unlocked_amount).map_err(|_| LockerError::MathOverflow)?;
+
+        let unlocked_amount = self.cliff_unlock_amount.safe_add(linear_unlocked_amount)?;
+
+        Ok(unlocked_amount)
+    }
+
+    /// Time from which the unlocked amount stops growing, the cancellation time at the latest.
+    pub fn get_vesting_end_time(&self) -> Result<u64> {
+        let schedule_kind = ScheduleKind::try_from(self.schedule_kind)
+            .map_err(|_| LockerError::InvalidScheduleKind)?;
+        let end_time = match schedule_kind {
+            ScheduleKind::Periodic | ScheduleKind::Linear => self
+                .cliff_time
+                .safe_add(self.frequency.safe_mul(self.number_of_period)?)?,
+            ScheduleKind::Custom => return Err(LockerError::MissingVestingSchedule.into()),
+            ScheduleKind::PeriodicFromStart => self
+                .vesting_start_time
+                .safe_add(self.frequency.safe_mul(self.number_of_period)?)?
+                .max(self.cliff_time),
+            ScheduleKind::CalendarMonthly => calendar::add_months(
+                self.cliff_time,
+                self.frequency.safe_mul(self.number_of_period)?,
+            )
+            .ok_or(LockerError::MathOverflow)?,
+        };
+        if self.is_cancelled() {
+            return Ok(end_time.min(self.cancelled_at));
+        }
+        Ok(end_time)
+    }
+
+    /// Like [Self::get_vesting_end_time], reading custom schedules from `vesting_schedule`.
+    pub fn get_vesting_end_time_with_schedule(
+        &self,
+        vesting_schedule: Option<&VestingSchedule>,
+    ) -> Result<u64> {
+        if self.schedule_kind != u8::from(ScheduleKind::Custom) {
+            return self.get_vesting_end_time();
+        }
+        let vesting_schedule = vesting_schedule.ok_or(LockerError::MissingVestingSchedule)?;
+        let end_time = vesting_schedule.get_vesting_end_time();
+        if self.is_cancelled() {
+            return Ok(end_time.min(self.cancelled_at));
+        }
+        Ok(end_time)
+    }
+
+    /// First timestamp after `current_ts` at which more tokens unlock, `None` once
+    /// the schedule has ended. Linear schedules unlock every second.
+    pub fn get_next_unlock_time(&self, current_ts: u64) -> Result<Option<u64>> {
+        let end_time = self.get_vesting_end_time()?;
+        if current_ts < self.cliff_time {
+            return Ok(Some(self.cliff_time).filter(|cliff_time| *cliff_time <= end_time));
+        }
+        if current_ts >= end_time {
+            return Ok(None);
+        }
+        let schedule_kind = ScheduleKind::try_from(self.schedule_kind)
+            .map_err(|_| LockerError::InvalidScheduleKind)?;
+        let next_unlock_time = match schedule_kind {
+            ScheduleKind::Periodic => {
+                let period = current_ts
+                    .safe_sub(self.cliff_time)?
+                    .safe_div(self.frequency)?
+                    .safe_add(1)?;
+                self.cliff_time.safe_add(period.safe_mul(self.frequency)?)?
+            }
+            ScheduleKind::Linear => current_ts.safe_add(1)?,
+            ScheduleKind::Custom => return Err(LockerError::MissingVestingSchedule.into()),
+            ScheduleKind::PeriodicFromStart => {
+                let period = current_ts
+                    .safe_sub(self.vesting_start_time)?
+                    .safe_div(self.frequency)?
+                    .safe_add(1)?;
+                self.vesting_start_time
+                    .safe_add(period.safe_mul(self.frequency)?)?
+            }
+            ScheduleKind::CalendarMonthly => {
+                let period = calendar::months_between(self.cliff_time, current_ts)
+                    .safe_div(self.frequency)?
+                    .safe_add(1)?;
+                calendar::add_months(self.cliff_time, period.safe_mul(self.frequency)?)
+                    .ok_or(LockerError::MathOverflow)?
+            }
+        };
+        // a cancelled escrow stops unlocking at the cancellation time
+        Ok(Some(next_unlock_time).filter(|next_unlock_time| *next_unlock_time <= end_time))
+    }
+
+    /// Like [Self::get_next_unlock_time], reading custom schedules from `vesting_schedule`.
+    pub fn get_next_unlock_time_with_schedule(
+        &self,
+        current_ts: u64,
+        vesting_schedule: Option<&VestingSchedule>,
+    ) -> Result<Option<u64>> {
+        if self.schedule_kind != u8::from(ScheduleKind::Custom) {
+            return self.get_next_unlock_time(current_ts);
+        }
+        let end_time = self.get_vesting_end_time_with_schedule(vesting_schedule)?;
+        let vesting_schedule = vesting_schedule.ok_or(LockerError::MissingVestingSchedule)?;
+        Ok(vesting_schedule
+            .get_next_unlock_time(current_ts)
+            .filter(|next_unlock_time| *next_unlock_time <= end_time))
+    }
+
+    /// Amount still to unlock after `current_ts`.
+    pub fn get_locked_amount(&self, current_ts: u64) -> Result<u64> {
+        let end_time = self.get_vesting_end_time()?;
+        let total_unlocked_amount = self.get_max_unlocked_amount(end_time)?;
+        let unlocked_amount = self.get_max_unlocked_amount(current_ts)?;
+        let locked_amount = total_unlocked_amount.safe_sub(unlocked_amount)?;
+        Ok(locked_amount)
+    }
+
+    /// Like [Self::get_locked_amount], reading custom schedules from `vesting_schedule`.
+    pub fn get_locked_amount_with_schedule(
+        &self,
+        current_ts: u64,
+        vesting_schedule: Option<&VestingSchedule>,
+    ) -> Result<u64> {
+        let end_time = self.get_vesting_end_time_with_schedule(vesting_schedule)?;
+        let total_unlocked_amount =
+            self.get_max_unlocked_amount_with_schedule(end_time, vesting_schedule)?;
+        let unlocked_amount =
+            self.get_max_unlocked_amount_with_schedule(current_ts, vesting_schedule)?;
+        let locked_amount = total_unlocked_amount.safe_sub(unlocked_amount)?;
+        Ok(locked_amount)
+    }
+
+    pub fn is_fully_vested(&self, current_ts: u64) -> Result<bool> {
+        Ok(current_ts >= self.get_vesting_end_time()?)
+    }
+
+    pub fn get_total_deposit_amount(&self) -> Result<u64> {
+        let total_amount = self
+            .cliff_unlock_amount
+            .safe_add(self.amount_per_period.safe_mul(self.number_of_period)?)?;
+        Ok(total_amount)
+    }
+
+    /// Raise `number_of_period` and `amount_per_period` to the given values, keeping the
+    /// amount unlocked at every timestamp up to `current_ts` unchanged.
+    /// Returns the additional amount the escrow must be funded with.
+    pub fn extend(
+        &mut self,
+        current_ts: u64,
+        number_of_period: u64,
+        amount_per_period: u64,
+    ) -> Result<u64> {
+        require!(
+            number_of_period >= self.number_of_period
+                && amount_per_period >= self.amount_per_period
+                && (number_of_period > self.number_of_period
+                    || amount_per_period > self.amount_per_period),
+            LockerError::InvalidExtendParameters
+        );
+
+        let schedule_kind = ScheduleKind::try_from(self.schedule_kind)
+            .map_err(|_| LockerError::InvalidScheduleKind)?;
+        let elapsed = current_ts.saturating_sub(self.cliff_time);
+        // a larger amount per period changes every past unlock after the first period,
+        // and more periods change past unlocks once the schedule has ended
+        let (has_started, has_ended) = match schedule_kind {
+            ScheduleKind::Periodic => {
+                let period = elapsed.safe_div(self.frequency)?;
+                (period > 0, period > self.number_of_period)
+            }
+            ScheduleKind::CalendarMonthly => {
+                let period = calendar::months_between(self.cliff_time, current_ts)
+                    .safe_div(self.frequency)?;
+                (period > 0, period > self.number_of_period)
+            }
+            ScheduleKind::Linear => {
+                let total_duration =
+                    u128::from(self.frequency).safe_mul(self.number_of_period.into())?;
+                (elapsed > 0, u128::from(elapsed) > total_duration)
+            }
+            // nothing unlocks before the cliff, after it periods count from the start
+            ScheduleKind::PeriodicFromStart => {
+                let period = current_ts
+                    .saturating_sub(self.vesting_start_time)
+                    .safe_div(self.frequency)?;
+                let has_passed_cliff = current_ts >= self.cliff_time;
+                (
+                    has_passed_cliff && period > 0,
+                    has_passed_cliff && period > self.number_of_period,
+                )
+            }
+            ScheduleKind::Custom => return Err(LockerError::InvalidScheduleKind.into()),
+        };
+        if amount_per_period > self.amount_per_period {
+            require!(!has_started, LockerError::ExtendChangesUnlockedAmount);
+        }
+        if number_of_period > self.number_of_period {
+            require!(!has_ended, LockerError::ExtendChangesUnlockedAmount);
+        }
+
+        let old_total_deposit_amount = self.get_total_deposit_amount()?;
+        self.number_of_period = number_of_period;
+        self.amount_per_period = amount_per_period;
+        let deposit_amount = self
+            .get_total_deposit_amount()?
+            .safe_sub(old_total_deposit_amount)?;
+
+        Ok(deposit_amount)
+    }
+
     pub fn get_claimable_amount(&self, current_ts: u64) -> Result<u64> {
         let max_unlocked_amount = self.get_max_unlocked_amount(current_ts)?;
         let claimable_amount = max_unlocked_amount.safe_sub(self.total_claimed_amount)?;
         Ok(claimable_amount)
     }
 
+    pub fn get_claimable_amount_with_schedule(
+        &self,
+        current_ts: u64,
+        vesting_schedule: Option<&VestingSchedule>,
+    ) -> Result<u64> {
+        let max_unlocked_amount =
+            self.get_max_unlocked_amount_with_schedule(current_ts, vesting_schedule)?;
+        let claimable_amount = max_unlocked_amount.safe_sub(self.total_claimed_amount)?;
+        Ok(claimable_amount)
+    }
+
     pub fn accumulate_claimed_amount(&mut self, claimed_amount: u64) -> Result<()> {
         self.total_claimed_amount = self.total_claimed_amount.safe_add(claimed_amount)?;
         Ok(())
     }
 
+    pub fn validate_update_recipient_signer(&self, signer: Pubkey) -> Result<()> {
+        let update_recipient_mode = UpdateRecipientMode::try_from(self.update_recipient_mode)
+            .map_err(|_| LockerError::InvalidUpdateRecipientMode)?;
+
+        let (allow_creator, allow_recipient) = match update_recipient_mode {
+            UpdateRecipientMode::NeitherCreatorOrRecipient => (false, false),
+            UpdateRecipientMode::OnlyCreator => (true, false),
+            UpdateRecipientMode::OnlyRecipient => (false, true),
+            UpdateRecipientMode::EitherCreatorAndRecipient => (true, true),
+        };
+        self.validate_signer(signer, allow_creator, allow_recipient)
+    }
+
+    pub fn validate_cancel_signer(&self, signer: Pubkey) -> Result<()> {
+        let cancel_mode =
+            CancelMode::try_from(self.cancel_mode).map_err(|_| LockerError::InvalidCancelMode)?;
+
+        let (allow_creator, allow_recipient) = match cancel_mode {
+            CancelMode::NeitherCreatorOrRecipient => (false, false),
+            CancelMode::OnlyCreator => (true, false),
+            CancelMode::OnlyRecipient => (false, true),
+            CancelMode::EitherCreatorAndRecipient => (true, true),
+        };
+        self.validate_signer(signer, allow_creator, allow_recipient)
+    }
+
+    fn validate_signer(
+        &self,
+        signer: Pubkey,
+        allow_creator: bool,
+        allow_recipient: bool,
+    ) -> Result<()> {
+        require!(
+            (allow_creator && signer == self.creator)
+                || (allow_recipient && signer == self.recipient),
+            LockerError::NotPermitToDoThisAction
+        );
+        Ok(())
+    }
+
     pub fn update_recipient(&mut self, new_recipient: Pubkey) {
         self.recipient = new_recipient;
+        // a direct update supersedes any pending change
+        self.cancel_pending_recipient();
+        // the delegate was chosen by the old recipient
+        self.revoke_claim_delegate();
+    }
+
+    pub fn has_claim_delegate(&self) -> bool {
+        self.claim_delegate != Pubkey::default()
+    }
+
+    pub fn set_claim_delegate(&mut self, claim_delegate: Pubkey) -> Result<()> {
+        require!(
+            claim_delegate != Pubkey::default() && claim_delegate != self.recipient,
+            LockerError::InvalidClaimDelegate
+        );
+        self.claim_delegate = claim_delegate;
+        Ok(())
+    }
+
+    pub fn revoke_claim_delegate(&mut self) {
+        self.claim_delegate = Pubkey::default();
+    }
+
+    pub fn has_pending_recipient(&self) -> bool {
+        self.pending_recipient != Pubkey::default()
+    }
+
+    /// Pending recipient still waiting for acceptance.
+    pub fn has_proposed_recipient(&self) -> bool {
+        self.has_pending_recipient() && self.recipient_change_effective_at == 0
+    }
+
+    /// Pending recipient taking over at `recipient_change_effective_at`.
+    pub fn has_scheduled_recipient(&self) -> bool {
+        self.has_pending_recipient() && self.recipient_change_effective_at != 0
+    }
+
+    pub fn propose_recipient(&mut self, pending_recipient: Pubkey) {
+        // once proposals are in use, one-step updates would bypass the acceptance
+        self.recipient_acceptance_required = true;
+        self.pending_recipient = pending_recipient;
+        self.recipient_change_effective_at = 0;
+    }
+
+    /// Change the recipient now, or schedule it after the escrow's delay.
+    /// Returns the time the change takes effect.
+    pub fn change_recipient(&mut self, new_recipient: Pubkey, current_ts: u64) -> Result<u64> {
+        if self.recipient_change_delay == 0 {
+            self.update_recipient(new_recipient);
+            return Ok(current_ts);
+        }
+        let effective_at = current_ts.safe_add(self.recipient_change_delay)?;
+        self.pending_recipient = new_recipient;
+        self.recipient_change_effective_at = effective_at;
+        Ok(effective_at)
+    }
+
+    /// Apply the scheduled recipient once it is due, returning the old recipient.
+    pub fn apply_scheduled_recipient(&mut self, current_ts: u64) -> Option<Pubkey> {
+        if !self.has_scheduled_recipient() || current_ts < self.recipient_change_effective_at {
+            return None;
+        }
+        let old_recipient = self.recipient;
+        self.update_recipient(self.pending_recipient);
+        Some(old_recipient)
+    }
+
+    pub fn cancel_pending_recipient(&mut self) {
+        self.pending_recipient = Pubkey::default();
+        self.recipient_change_effective_at = 0;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    pub fn cancel(&mut self, current_ts: u64) {
+        self.cancelled = true;
+        self.cancelled_at = current_ts;
     }
 }
 
 #[cfg(test)]
 mod escrow_test {
     use super::*;
+    use proptest::prelude::any;
     use proptest::proptest;
+    use proptest::sample::select;
 
     proptest! {
     #[test]
@@ -126,12 +671,14 @@ mod escrow_test {
         cliff_unlock_amount in 0..u64::MAX / 100,
         amount_per_period in 0..u64::MAX / 10000,
     ) {
-        let mut escrow = VestingEscrow::default();
-        escrow.cliff_time = cliff_time;
-        escrow.frequency = frequency;
-        escrow.number_of_period = number_of_period;
-        escrow.cliff_unlock_amount = cliff_unlock_amount;
-        escrow.amount_per_period = amount_per_period;
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            ..Default::default()
+        };
 
         let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time - 1).unwrap();
         assert_eq!(unlocked_amount, 0);
@@ -140,9 +687,12 @@ mod escrow_test {
         assert_eq!(unlocked_amount, cliff_unlock_amount);
 
         let unlocked_amount = escrow
-            .get_max_unlocked_amount(cliff_time + frequency * 1)
+            .get_max_unlocked_amount(cliff_time + frequency)
             .unwrap();
-        assert_eq!(unlocked_amount, cliff_unlock_amount + amount_per_period * 1);
+        assert_eq!(
+            unlocked_amount,
+            cliff_unlock_amount + amount_per_period * number_of_period.min(1)
+        );
 
         let unlocked_amount = escrow
             .get_max_unlocked_amount(cliff_time + frequency * number_of_period - 1)
@@ -173,4 +723,515 @@ mod escrow_test {
         );
         }
     }
+
+    proptest! {
+    #[test]
+    fn test_get_max_unlocked_amount_after_cancel(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 1..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 10000,
+        cancelled_period in 0..10000u64,
+    ) {
+        let mut escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            ..Default::default()
+        };
+
+        let cancelled_at = cliff_time + frequency * cancelled_period;
+        let unlocked_at_cancel = escrow.get_max_unlocked_amount(cancelled_at).unwrap();
+        escrow.cancel(cancelled_at);
+
+        let unlocked_amount = escrow.get_max_unlocked_amount(cancelled_at).unwrap();
+        assert_eq!(unlocked_amount, unlocked_at_cancel);
+
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(cliff_time + frequency * number_of_period + 1)
+            .unwrap();
+        assert_eq!(unlocked_amount, unlocked_at_cancel);
+
+        let unlocked_amount = escrow.get_max_unlocked_amount(u64::MAX).unwrap();
+        assert_eq!(unlocked_amount, unlocked_at_cancel);
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_get_max_unlocked_amount_linear(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 20000,
+        elapsed in 0..2592000u64,
+    ) {
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind: ScheduleKind::Linear.into(),
+            ..Default::default()
+        };
+
+        let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time - 1).unwrap();
+        assert_eq!(unlocked_amount, 0);
+
+        let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time).unwrap();
+        assert_eq!(unlocked_amount, cliff_unlock_amount);
+
+        // matches the periodic schedule on every period boundary
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(cliff_time + frequency)
+            .unwrap();
+        assert_eq!(
+            unlocked_amount,
+            cliff_unlock_amount + amount_per_period * number_of_period.min(1)
+        );
+
+        // never behind the periodic schedule and never decreasing in between
+        let current_ts = cliff_time + elapsed;
+        let unlocked_amount = escrow.get_max_unlocked_amount(current_ts).unwrap();
+        let periodic_escrow = VestingEscrow {
+            schedule_kind: ScheduleKind::Periodic.into(),
+            ..escrow
+        };
+        assert!(unlocked_amount >= periodic_escrow.get_max_unlocked_amount(current_ts).unwrap());
+        assert!(escrow.get_max_unlocked_amount(current_ts + 1).unwrap() >= unlocked_amount);
+
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(cliff_time + frequency * number_of_period)
+            .unwrap();
+        assert_eq!(
+            unlocked_amount,
+            cliff_unlock_amount + amount_per_period * number_of_period
+        );
+
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(cliff_time + frequency * number_of_period + 1)
+            .unwrap();
+        assert_eq!(
+            unlocked_amount,
+            cliff_unlock_amount + amount_per_period * number_of_period
+        );
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_extend_keeps_past_unlocked_amounts(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 100000,
+        schedule_kind in select(vec![0u8, 1, 3]),
+        start_offset in 0..2592000u64 * 10001,
+        elapsed in 0..2592000u64 * 10000,
+        additional_number_of_period in 0..10000u64,
+        additional_amount_per_period in 0..u64::MAX / 100000,
+        raise_amount_per_period: bool,
+    ) {
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind,
+            vesting_start_time: cliff_time.saturating_sub(start_offset),
+            ..Default::default()
+        };
+        let current_ts = cliff_time + elapsed;
+
+        let mut extended_escrow = escrow.clone();
+        let result = extended_escrow.extend(
+            current_ts,
+            number_of_period + additional_number_of_period,
+            amount_per_period + additional_amount_per_period * u64::from(raise_amount_per_period),
+        );
+        let Ok(deposit_amount) = result else {
+            assert_eq!(extended_escrow.number_of_period, number_of_period);
+            assert_eq!(extended_escrow.amount_per_period, amount_per_period);
+            return Ok(());
+        };
+
+        assert_eq!(
+            extended_escrow.get_total_deposit_amount().unwrap(),
+            escrow.get_total_deposit_amount().unwrap() + deposit_amount
+        );
+        for i in 0..=16 {
+            let ts = cliff_time - 1 + (elapsed + 1) / 16 * i;
+            assert_eq!(
+                extended_escrow.get_max_unlocked_amount(ts).unwrap(),
+                escrow.get_max_unlocked_amount(ts).unwrap()
+            );
+        }
+        assert_eq!(
+            extended_escrow.get_max_unlocked_amount(current_ts).unwrap(),
+            escrow.get_max_unlocked_amount(current_ts).unwrap()
+        );
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_schedule_queries(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 20000,
+        schedule_kind in select(vec![0u8, 1, 3]),
+        start_offset in 0..2592000u64 * 10001,
+        elapsed in 0..2592000u64 * 10001,
+    ) {
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind,
+            vesting_start_time: cliff_time.saturating_sub(start_offset),
+            ..Default::default()
+        };
+        let total_deposit_amount = escrow.get_total_deposit_amount().unwrap();
+        let end_time = escrow.get_vesting_end_time().unwrap();
+
+        // everything is unlocked from the end time on, and not a second earlier
+        assert_eq!(escrow.get_max_unlocked_amount(end_time).unwrap(), total_deposit_amount);
+        assert_eq!(escrow.get_max_unlocked_amount(u64::MAX).unwrap(), total_deposit_amount);
+        assert!(escrow.is_fully_vested(end_time).unwrap());
+        assert!(!escrow.is_fully_vested(end_time - 1).unwrap());
+        assert_eq!(escrow.get_locked_amount(end_time).unwrap(), 0);
+        assert!(escrow.get_next_unlock_time(end_time).unwrap().is_none());
+
+        for current_ts in [cliff_time - 1, cliff_time, cliff_time - 1 + elapsed] {
+            let unlocked_amount = escrow.get_max_unlocked_amount(current_ts).unwrap();
+            assert_eq!(
+                escrow.get_locked_amount(current_ts).unwrap(),
+                total_deposit_amount - unlocked_amount
+            );
+            assert_eq!(
+                escrow.is_fully_vested(current_ts).unwrap(),
+                current_ts >= end_time
+            );
+
+            match escrow.get_next_unlock_time(current_ts).unwrap() {
+                // nothing unlocks strictly between now and the next unlock time
+                Some(next_unlock_time) => {
+                    assert!(next_unlock_time > current_ts && next_unlock_time <= end_time);
+                    assert_eq!(
+                        escrow.get_max_unlocked_amount(next_unlock_time - 1).unwrap(),
+                        unlocked_amount
+                    );
+                }
+                None => assert_eq!(unlocked_amount, total_deposit_amount),
+            }
+        }
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_schedule_queries_after_cancel(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 20000,
+        schedule_kind in select(vec![0u8, 1, 3]),
+        start_offset in 0..2592000u64 * 10001,
+        cancelled_after in 0..2592000u64 * 10001,
+        elapsed in 0..2592000u64 * 10001,
+    ) {
+        let mut escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind,
+            vesting_start_time: cliff_time.saturating_sub(start_offset),
+            ..Default::default()
+        };
+        escrow.cancel(cliff_time - 1 + cancelled_after);
+        let end_time = escrow.get_vesting_end_time().unwrap();
+        assert!(end_time <= escrow.cancelled_at);
+
+        let total_unlocked_amount = escrow.get_max_unlocked_amount(u64::MAX).unwrap();
+        assert_eq!(escrow.get_max_unlocked_amount(end_time).unwrap(), total_unlocked_amount);
+
+        let current_ts = cliff_time - 1 + elapsed;
+        let unlocked_amount = escrow.get_max_unlocked_amount(current_ts).unwrap();
+        assert_eq!(
+            escrow.get_locked_amount(current_ts).unwrap(),
+            total_unlocked_amount - unlocked_amount
+        );
+        match escrow.get_next_unlock_time(current_ts).unwrap() {
+            Some(next_unlock_time) => assert!(next_unlock_time <= end_time),
+            None => assert_eq!(unlocked_amount, total_unlocked_amount),
+        }
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_get_max_unlocked_amount_from_start(
+        cliff_time in 1..=u64::MAX/2,
+        frequency in 1..2592000u64,
+        number_of_period in 0..10000u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 20000,
+        start_offset in 0..2592000u64 * 10001,
+        elapsed in 0..2592000u64 * 10001,
+    ) {
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind: ScheduleKind::PeriodicFromStart.into(),
+            vesting_start_time: cliff_time.saturating_sub(start_offset),
+            ..Default::default()
+        };
+        let vesting_start_time = escrow.vesting_start_time;
+        let accrued_amount = |current_ts: u64| {
+            let period = (current_ts.max(vesting_start_time) - vesting_start_time) / frequency;
+            amount_per_period * period.min(number_of_period)
+        };
+
+        // accrued amounts stay locked until the cliff
+        let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time - 1).unwrap();
+        assert_eq!(unlocked_amount, 0);
+        assert_eq!(
+            escrow.get_accrued_locked_amount(cliff_time - 1).unwrap(),
+            accrued_amount(cliff_time - 1)
+        );
+        assert_eq!(escrow.get_locked_amount(cliff_time - 1).unwrap(), escrow.get_total_deposit_amount().unwrap());
+
+        // and are released at once at the cliff
+        let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time).unwrap();
+        assert_eq!(unlocked_amount, cliff_unlock_amount + accrued_amount(cliff_time));
+        assert_eq!(escrow.get_accrued_locked_amount(cliff_time).unwrap(), 0);
+
+        let current_ts = cliff_time + elapsed;
+        let unlocked_amount = escrow.get_max_unlocked_amount(current_ts).unwrap();
+        assert_eq!(unlocked_amount, cliff_unlock_amount + accrued_amount(current_ts));
+        assert!(escrow.get_max_unlocked_amount(current_ts + 1).unwrap() >= unlocked_amount);
+
+        // without an accrual period before the cliff, it is the periodic schedule
+        let periodic_escrow = VestingEscrow {
+            schedule_kind: ScheduleKind::Periodic.into(),
+            ..escrow.clone()
+        };
+        if vesting_start_time == cliff_time {
+            assert_eq!(periodic_escrow.get_max_unlocked_amount(current_ts).unwrap(), unlocked_amount);
+        }
+
+        let unlocked_amount = escrow
+            .get_max_unlocked_amount(vesting_start_time + frequency * number_of_period)
+            .unwrap();
+        if vesting_start_time + frequency * number_of_period >= cliff_time {
+            assert_eq!(unlocked_amount, escrow.get_total_deposit_amount().unwrap());
+        }
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_get_max_unlocked_amount_calendar_monthly(
+        cliff_time in 1..=4_102_444_800u64,
+        frequency in 1..=12u64,
+        number_of_period in 0..600u64,
+        cliff_unlock_amount in 0..u64::MAX / 100,
+        amount_per_period in 0..u64::MAX / 1000,
+        period in 0..600u64,
+    ) {
+        let escrow = VestingEscrow {
+            cliff_time,
+            frequency,
+            number_of_period,
+            cliff_unlock_amount,
+            amount_per_period,
+            schedule_kind: ScheduleKind::CalendarMonthly.into(),
+            ..Default::default()
+        };
+
+        let unlocked_amount = escrow.get_max_unlocked_amount(cliff_time - 1).unwrap();
+        assert_eq!(unlocked_amount, 0);
+
+        // unlocks on the same day and time of every `frequency`-th month
+        let unlock_time = calendar::add_months(cliff_time, period * frequency).unwrap();
+        let unlocked_amount = escrow.get_max_unlocked_amount(unlock_time).unwrap();
+        assert_eq!(
+            unlocked_amount,
+            cliff_unlock_amount + amount_per_period * period.min(number_of_period)
+        );
+        if period > 0 {
+            let unlocked_amount = escrow.get_max_unlocked_amount(unlock_time - 1).unwrap();
+            assert_eq!(
+                unlocked_amount,
+                cliff_unlock_amount + amount_per_period * (period - 1).min(number_of_period)
+            );
+        }
+
+        let end_time = escrow.get_vesting_end_time().unwrap();
+        assert_eq!(
+            escrow.get_max_unlocked_amount(end_time).unwrap(),
+            escrow.get_total_deposit_amount().unwrap()
+        );
+        let next_unlock_time = escrow.get_next_unlock_time(unlock_time).unwrap();
+        if period < number_of_period {
+            assert_eq!(
+                next_unlock_time,
+                calendar::add_months(cliff_time, (period + 1) * frequency)
+            );
+        } else {
+            assert_eq!(next_unlock_time, None);
+        }
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_get_current_time(
+        slot in 0..u64::MAX,
+        unix_timestamp in i64::MIN..i64::MAX,
+        time_unit in 0..=1u8,
+    ) {
+        let escrow = VestingEscrow {
+            time_unit,
+            ..Default::default()
+        };
+        let clock = Clock {
+            slot,
+            unix_timestamp,
+            ..Default::default()
+        };
+
+        let current_time = escrow.get_current_time(&clock);
+        if time_unit == u8::from(TimeUnit::Slot) {
+            assert_eq!(current_time.unwrap(), slot);
+        } else if unix_timestamp < 0 {
+            assert!(current_time.is_err());
+        } else {
+            assert_eq!(current_time.unwrap(), unix_timestamp as u64);
+        }
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_recipient_change_revokes_claim_delegate(
+        recipient_change_delay in 0..1_000_000u64,
+        current_ts in 0..1_000_000u64,
+    ) {
+        let recipient = Pubkey::new_unique();
+        let claim_delegate = Pubkey::new_unique();
+        let mut escrow = VestingEscrow {
+            recipient,
+            recipient_change_delay,
+            ..Default::default()
+        };
+
+        assert!(escrow.set_claim_delegate(Pubkey::default()).is_err());
+        assert!(escrow.set_claim_delegate(recipient).is_err());
+        escrow.set_claim_delegate(claim_delegate).unwrap();
+        assert!(escrow.has_claim_delegate());
+
+        // the delegate stays until the new recipient actually takes over
+        let effective_at = escrow
+            .change_recipient(Pubkey::new_unique(), current_ts)
+            .unwrap();
+        assert_eq!(escrow.has_claim_delegate(), recipient_change_delay != 0);
+        escrow.apply_scheduled_recipient(effective_at);
+        assert_eq!(escrow.claim_delegate, Pubkey::default());
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_get_escrow_token_address(escrow in any::<[u8; 32]>()) {
+        let escrow = Pubkey::new_from_array(escrow);
+        let token_program = anchor_spl::token::ID;
+        let mut vesting_escrow = VestingEscrow {
+            token_mint: Pubkey::new_unique(),
+            ..Default::default()
+        };
+        assert_eq!(
+            vesting_escrow.get_escrow_token_address(&escrow, &token_program).unwrap(),
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &escrow,
+                &vesting_escrow.token_mint,
+                &token_program,
+            )
+        );
+
+        let (vault, vault_bump) =
+            Pubkey::find_program_address(&[b"vault".as_ref(), escrow.as_ref()], &crate::ID);
+        vesting_escrow.set_pda_vault(vault_bump);
+        assert_eq!(
+            vesting_escrow.get_escrow_token_address(&escrow, &token_program).unwrap(),
+            vault
+        );
+        }
+    }
+
+    proptest! {
+    #[test]
+    fn test_validate_cancel_signer(cancel_mode in 0..=5u8) {
+        let creator = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let escrow = VestingEscrow {
+            creator,
+            recipient,
+            cancel_mode,
+            ..Default::default()
+        };
+
+        if let Ok(cancel_mode) = CancelMode::try_from(cancel_mode) {
+            let allow_creator = matches!(
+                cancel_mode,
+                CancelMode::OnlyCreator | CancelMode::EitherCreatorAndRecipient
+            );
+            let allow_recipient = matches!(
+                cancel_mode,
+                CancelMode::OnlyRecipient | CancelMode::EitherCreatorAndRecipient
+            );
+            assert_eq!(escrow.validate_cancel_signer(creator).is_ok(), allow_creator);
+            assert_eq!(escrow.validate_cancel_signer(recipient).is_ok(), allow_recipient);
+            assert!(escrow.validate_cancel_signer(Pubkey::new_unique()).is_err());
+        } else {
+            assert_eq!(
+                escrow.validate_cancel_signer(creator).unwrap_err(),
+                LockerError::InvalidCancelMode.into()
+            );
+        }
+        }
+    }
+
+    #[test]
+    fn test_cancel_at_time_zero() {
+        // slot clocks start at 0, so the cancellation time cannot double as the flag
+        let mut escrow = VestingEscrow {
+            cliff_unlock_amount: 5,
+            amount_per_period: 1,
+            number_of_period: 10,
+            frequency: 1,
+            time_unit: TimeUnit::Slot.into(),
+            ..Default::default()
+        };
+        escrow.cancel(0);
+        assert!(escrow.is_cancelled());
+        assert_eq!(escrow.get_max_unlocked_amount(100).unwrap(), 5);
+    }
 }
@@ -1,4 +1,63 @@
 use crate::*;
+use anchor_lang::Discriminator;
+use static_assertions::const_assert;
+
+// The limits below default to the values given and can be set per deployment by building
+// with the matching `LOCKER_MAX_METADATA_*` environment variable.
+
+/// Maximum length in bytes of [VestingEscrowMetadata::name].
+pub const MAX_METADATA_NAME_LENGTH: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_NAME_LENGTH"), 64);
+/// Maximum length in bytes of [VestingEscrowMetadata::description].
+pub const MAX_METADATA_DESCRIPTION_LENGTH: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_DESCRIPTION_LENGTH"), 512);
+/// Maximum length in bytes of the creator and recipient emails.
+pub const MAX_METADATA_EMAIL_LENGTH: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_EMAIL_LENGTH"), 254);
+/// Maximum length in bytes of [VestingEscrowMetadata::uri].
+pub const MAX_METADATA_URI_LENGTH: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_URI_LENGTH"), 200);
+/// Maximum number of [VestingEscrowMetadata::attributes].
+pub const MAX_METADATA_ATTRIBUTES: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_ATTRIBUTES"), 16);
+/// Maximum length in bytes of a [MetadataAttribute] key.
+pub const MAX_METADATA_ATTRIBUTE_KEY_LENGTH: usize =
+    build_limit(option_env!("LOCKER_MAX_METADATA_ATTRIBUTE_KEY_LENGTH"), 32);
+/// Maximum length in bytes of a [MetadataAttribute] value.
+pub const MAX_METADATA_ATTRIBUTE_VALUE_LENGTH: usize = build_limit(
+    option_env!("LOCKER_MAX_METADATA_ATTRIBUTE_VALUE_LENGTH"),
+    256,
+);
+
+// a metadata account at its limits must still fit in a single allocation or realloc
+const_assert!(
+    8 + VestingEscrowMetadata::MAX_SPACE
+        <= anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE
+);
+
+/// Parse a limit given at build time, falling back to `default` when it is not set.
+const fn build_limit(value: Option<&str>, default: usize) -> usize {
+    let Some(value) = value else {
+        return default;
+    };
+    let bytes = value.as_bytes();
+    assert!(!bytes.is_empty(), "metadata limit must be a number");
+    let mut limit = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "metadata limit must be a number");
+        limit = limit * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+    limit
+}
+
+/// Free-form key/value pair attached to an escrow, such as a document hash.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct MetadataAttribute {
+    pub key: String,
+    pub value: String,
+}
 
 /// Metadata about an escrow.
 #[account]
@@ -14,19 +73,326 @@ pub struct VestingEscrowMetadata {
     pub creator_email: String,
     /// Email of recipient
     pub recipient_email: String,
+    /// URI of off-chain metadata, such as a grant letter
+    pub uri: String,
+    /// Additional attributes, keys are unique
+    pub attributes: Vec<MetadataAttribute>,
+}
+
+/// Layout of metadata accounts created before `uri` and `attributes` were added.
+#[derive(AnchorDeserialize)]
+struct LegacyVestingEscrowMetadata {
+    _escrow: Pubkey,
+    _name: String,
+    _description: String,
+    _creator_email: String,
+    _recipient_email: String,
 }
 
 impl VestingEscrowMetadata {
+    /// Bytes appended to a legacy account, zeroed they encode an empty `uri` and `attributes`.
+    pub const LEGACY_MIGRATION_SPACE: usize = 4 + 4;
+
+    /// Whether `data` holds a metadata account in the legacy layout, which cannot be loaded
+    /// until it is migrated.
+    pub fn is_legacy(data: &[u8]) -> bool {
+        let Some(mut rest) = data.strip_prefix(&Self::DISCRIMINATOR[..]) else {
+            return false;
+        };
+        LegacyVestingEscrowMetadata::deserialize(&mut rest).is_ok() && rest.is_empty()
+    }
+
+    /// Space of an account with every field and attribute at its limit.
+    pub const MAX_SPACE: usize = std::mem::size_of::<Pubkey>()
+        + 4
+        + MAX_METADATA_NAME_LENGTH
+        + 4
+        + MAX_METADATA_DESCRIPTION_LENGTH
+        + 4
+        + MAX_METADATA_EMAIL_LENGTH * 2
+        + 4
+        + 4
+        + MAX_METADATA_URI_LENGTH
+        + 4
+        + MAX_METADATA_ATTRIBUTES
+            * (4 + MAX_METADATA_ATTRIBUTE_KEY_LENGTH + 4 + MAX_METADATA_ATTRIBUTE_VALUE_LENGTH);
+
     /// Space that a [EscrowMetadata] takes up.
     pub fn space(metadata: &CreateVestingEscrowMetadataParameters) -> usize {
         std::mem::size_of::<Pubkey>()
             + 4
-            + metadata.name.as_bytes().len()
+            + metadata.name.len()
+            + 4
+            + metadata.description.len()
+            + 4
+            + metadata.creator_email.len()
             + 4
-            + metadata.description.as_bytes().len()
+            + metadata.recipient_email.len()
             + 4
-            + metadata.creator_email.as_bytes().len()
             + 4
-            + metadata.recipient_email.as_bytes().len()
+    }
+
+    /// Space the account takes up with its current fields and attributes.
+    pub fn get_space(&self) -> usize {
+        std::mem::size_of::<Pubkey>()
+            + 4
+            + self.name.len()
+            + 4
+            + self.description.len()
+            + 4
+            + self.creator_email.len()
+            + 4
+            + self.recipient_email.len()
+            + 4
+            + self.uri.len()
+            + 4
+            + self
+                .attributes
+                .iter()
+                .map(|attribute| 4 + attribute.key.len() + 4 + attribute.value.len())
+                .sum::<usize>()
+    }
+
+    pub fn validate(metadata: &CreateVestingEscrowMetadataParameters) -> Result<()> {
+        require!(
+            metadata.name.len() <= MAX_METADATA_NAME_LENGTH,
+            LockerError::MetadataNameTooLong
+        );
+        require!(
+            metadata.description.len() <= MAX_METADATA_DESCRIPTION_LENGTH,
+            LockerError::MetadataDescriptionTooLong
+        );
+        require!(
+            metadata.creator_email.len() <= MAX_METADATA_EMAIL_LENGTH,
+            LockerError::CreatorEmailTooLong
+        );
+        require!(
+            is_valid_email(&metadata.creator_email),
+            LockerError::InvalidCreatorEmail
+        );
+        Self::validate_recipient_email(&metadata.recipient_email)
+    }
+
+    pub fn validate_recipient_email(recipient_email: &str) -> Result<()> {
+        require!(
+            recipient_email.len() <= MAX_METADATA_EMAIL_LENGTH,
+            LockerError::RecipientEmailTooLong
+        );
+        require!(
+            is_valid_email(recipient_email),
+            LockerError::InvalidRecipientEmail
+        );
+        Ok(())
+    }
+
+    pub fn set_uri(&mut self, uri: String) -> Result<()> {
+        require!(
+            uri.len() <= MAX_METADATA_URI_LENGTH,
+            LockerError::MetadataUriTooLong
+        );
+        self.uri = uri;
+        Ok(())
+    }
+
+    /// Insert the attribute, or replace the value of an existing one with the same key.
+    pub fn set_attribute(&mut self, key: String, value: String) -> Result<()> {
+        require!(
+            !key.is_empty() && key.len() <= MAX_METADATA_ATTRIBUTE_KEY_LENGTH,
+            LockerError::InvalidMetadataAttributeKey
+        );
+        require!(
+            value.len() <= MAX_METADATA_ATTRIBUTE_VALUE_LENGTH,
+            LockerError::MetadataAttributeValueTooLong
+        );
+
+        if let Some(attribute) = self.attributes.iter_mut().find(|a| a.key == key) {
+            attribute.value = value;
+            return Ok(());
+        }
+        require!(
+            self.attributes.len() < MAX_METADATA_ATTRIBUTES,
+            LockerError::TooManyMetadataAttributes
+        );
+        self.attributes.push(MetadataAttribute { key, value });
+        Ok(())
+    }
+
+    pub fn remove_attribute(&mut self, key: &str) -> Result<()> {
+        let index = self
+            .attributes
+            .iter()
+            .position(|attribute| attribute.key == key)
+            .ok_or(LockerError::MetadataAttributeNotFound)?;
+        self.attributes.remove(index);
+        Ok(())
+    }
+}
+
+/// Basic `local@domain.tld` syntax check; an empty email means none was given.
+fn is_valid_email(email: &str) -> bool {
+    if email.is_empty() {
+        return true;
+    }
+    if email.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.split('.').count() > 1
+        && domain.split('.').all(|label| !label.is_empty())
+}
+
+#[cfg(test)]
+mod metadata_test {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email(""));
+        assert!(is_valid_email("recipient@example.com"));
+        assert!(is_valid_email("first.last+tag@mail.example.co"));
+
+        assert!(!is_valid_email("recipient"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("recipient@"));
+        assert!(!is_valid_email("recipient@example"));
+        assert!(!is_valid_email("recipient@.example.com"));
+        assert!(!is_valid_email("recipient@example.com."));
+        assert!(!is_valid_email("recipient@@example.com"));
+        assert!(!is_valid_email("re cipient@example.com"));
+    }
+
+    #[test]
+    fn test_build_limit() {
+        assert_eq!(build_limit(None, 64), 64);
+        assert_eq!(build_limit(Some("0"), 64), 0);
+        assert_eq!(build_limit(Some("128"), 64), 128);
+    }
+
+    #[test]
+    #[should_panic(expected = "metadata limit must be a number")]
+    fn test_build_limit_invalid() {
+        build_limit(Some("12a"), 64);
+    }
+
+    #[test]
+    fn test_validate() {
+        let metadata = CreateVestingEscrowMetadataParameters {
+            name: "a".repeat(MAX_METADATA_NAME_LENGTH),
+            description: "a".repeat(MAX_METADATA_DESCRIPTION_LENGTH),
+            creator_email: "creator@example.com".to_owned(),
+            recipient_email: String::new(),
+        };
+        VestingEscrowMetadata::validate(&metadata).unwrap();
+
+        let name = "a".repeat(MAX_METADATA_NAME_LENGTH + 1);
+        let result = VestingEscrowMetadata::validate(&CreateVestingEscrowMetadataParameters {
+            name,
+            ..metadata
+        });
+        assert_eq!(result.unwrap_err(), LockerError::MetadataNameTooLong.into());
+    }
+
+    #[test]
+    fn test_legacy_layout() {
+        let mut data = VestingEscrowMetadata::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        for field in [
+            "name",
+            "description",
+            "creator@example.com",
+            "recipient@example.com",
+        ] {
+            field.to_owned().serialize(&mut data).unwrap();
+        }
+        assert!(VestingEscrowMetadata::is_legacy(&data));
+        assert!(VestingEscrowMetadata::try_deserialize(&mut data.as_slice()).is_err());
+
+        data.resize(
+            data.len() + VestingEscrowMetadata::LEGACY_MIGRATION_SPACE,
+            0,
+        );
+        assert!(!VestingEscrowMetadata::is_legacy(&data));
+        let escrow_metadata = VestingEscrowMetadata::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(escrow_metadata.name, "name");
+        assert_eq!(escrow_metadata.recipient_email, "recipient@example.com");
+        assert!(escrow_metadata.uri.is_empty());
+        assert!(escrow_metadata.attributes.is_empty());
+        assert_eq!(8 + escrow_metadata.get_space(), data.len());
+    }
+
+    #[test]
+    fn test_attributes() {
+        let metadata = CreateVestingEscrowMetadataParameters {
+            name: "name".to_owned(),
+            description: "description".to_owned(),
+            creator_email: "creator@example.com".to_owned(),
+            recipient_email: "recipient@example.com".to_owned(),
+        };
+        let mut escrow_metadata = VestingEscrowMetadata {
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            creator_email: metadata.creator_email.clone(),
+            recipient_email: metadata.recipient_email.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            escrow_metadata.get_space(),
+            VestingEscrowMetadata::space(&metadata)
+        );
+
+        let uri = "https://example.com/grant.json";
+        escrow_metadata.set_uri(uri.to_owned()).unwrap();
+        let result = escrow_metadata.set_uri("a".repeat(MAX_METADATA_URI_LENGTH + 1));
+        assert_eq!(result.unwrap_err(), LockerError::MetadataUriTooLong.into());
+        assert_eq!(escrow_metadata.uri, uri);
+        escrow_metadata.set_uri(String::new()).unwrap();
+
+        escrow_metadata
+            .set_attribute("sha256".to_owned(), "a".repeat(64))
+            .unwrap();
+        escrow_metadata
+            .set_attribute("sha256".to_owned(), "b".repeat(64))
+            .unwrap();
+        assert_eq!(escrow_metadata.attributes.len(), 1);
+        assert_eq!(escrow_metadata.attributes[0].value, "b".repeat(64));
+        assert_eq!(
+            escrow_metadata.get_space(),
+            VestingEscrowMetadata::space(&metadata) + 4 + 6 + 4 + 64
+        );
+        assert_eq!(
+            escrow_metadata.try_to_vec().unwrap().len(),
+            escrow_metadata.get_space()
+        );
+
+        for i in 1..MAX_METADATA_ATTRIBUTES {
+            escrow_metadata
+                .set_attribute(i.to_string(), String::new())
+                .unwrap();
+        }
+        let result = escrow_metadata.set_attribute("key".to_owned(), String::new());
+        assert_eq!(
+            result.unwrap_err(),
+            LockerError::TooManyMetadataAttributes.into()
+        );
+        let result = escrow_metadata.set_attribute(String::new(), String::new());
+        assert_eq!(
+            result.unwrap_err(),
+            LockerError::InvalidMetadataAttributeKey.into()
+        );
+
+        escrow_metadata.remove_attribute("sha256").unwrap();
+        assert_eq!(
+            escrow_metadata.attributes.len(),
+            MAX_METADATA_ATTRIBUTES - 1
+        );
+        let result = escrow_metadata.remove_attribute("sha256");
+        assert_eq!(
+            result.unwrap_err(),
+            LockerError::MetadataAttributeNotFound.into()
+        );
     }
 }
@@ -0,0 +1,148 @@
+use crate::*;
+use arbitrary::Arbitrary;
+
+/// Maximum number of points in a [VestingSchedule].
+pub const MAX_SCHEDULE_POINTS: usize = 64;
+
+/// Cumulative amount unlocked at a timestamp.
+#[derive(AnchorSerialize, AnchorDeserialize, Arbitrary, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SchedulePoint {
+    /// unlock timestamp
+    pub timestamp: u64,
+    /// total amount unlocked from this timestamp on
+    pub cumulative_amount: u64,
+}
+
+/// Custom unlock schedule of an escrow.
+#[account]
+#[derive(Default, Debug)]
+pub struct VestingSchedule {
+    /// The [VestingEscrow].
+    pub escrow: Pubkey,
+    /// Points with strictly increasing timestamps and non-decreasing amounts.
+    pub points: Vec<SchedulePoint>,
+}
+
+impl VestingSchedule {
+    /// Space that a [VestingSchedule] with `num_points` points takes up.
+    pub fn space(num_points: usize) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + num_points * std::mem::size_of::<SchedulePoint>()
+    }
+
+    pub fn validate_points(points: &[SchedulePoint]) -> Result<()> {
+        require!(
+            !points.is_empty() && points.len() <= MAX_SCHEDULE_POINTS,
+            LockerError::InvalidSchedulePointCount
+        );
+        for window in points.windows(2) {
+            require!(
+                window[1].timestamp > window[0].timestamp,
+                LockerError::InvalidScheduleTimestamp
+            );
+            require!(
+                window[1].cumulative_amount >= window[0].cumulative_amount,
+                LockerError::InvalidScheduleAmount
+            );
+        }
+        Ok(())
+    }
+
+    pub fn get_total_deposit_amount(&self) -> u64 {
+        self.points
+            .last()
+            .map_or(0, |point| point.cumulative_amount)
+    }
+
+    pub fn get_max_unlocked_amount(&self, current_ts: u64) -> u64 {
+        self.points
+            .iter()
+            .take_while(|point| point.timestamp <= current_ts)
+            .last()
+            .map_or(0, |point| point.cumulative_amount)
+    }
+
+    pub fn get_vesting_end_time(&self) -> u64 {
+        self.points.last().map_or(0, |point| point.timestamp)
+    }
+
+    pub fn get_next_unlock_time(&self, current_ts: u64) -> Option<u64> {
+        self.points
+            .iter()
+            .map(|point| point.timestamp)
+            .find(|timestamp| *timestamp > current_ts)
+    }
+}
+
+#[cfg(test)]
+mod schedule_test {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::proptest;
+
+    proptest! {
+    #[test]
+    fn test_get_max_unlocked_amount(
+        start_time in 0..=u64::MAX/2,
+        steps in vec((1..2592000u64, 0..u64::MAX / 1000), 1..MAX_SCHEDULE_POINTS),
+    ) {
+        let mut points = vec![];
+        let mut timestamp = start_time;
+        let mut cumulative_amount = 0;
+        for (duration, amount) in steps {
+            timestamp += duration;
+            cumulative_amount += amount / MAX_SCHEDULE_POINTS as u64;
+            points.push(SchedulePoint {
+                timestamp,
+                cumulative_amount,
+            });
+        }
+        VestingSchedule::validate_points(&points).unwrap();
+
+        let schedule = VestingSchedule {
+            points: points.clone(),
+            ..Default::default()
+        };
+
+        let unlocked_amount = schedule.get_max_unlocked_amount(points[0].timestamp - 1);
+        assert_eq!(unlocked_amount, 0);
+
+        for window in points.windows(2) {
+            let unlocked_amount = schedule.get_max_unlocked_amount(window[0].timestamp);
+            assert_eq!(unlocked_amount, window[0].cumulative_amount);
+
+            let unlocked_amount = schedule.get_max_unlocked_amount(window[1].timestamp - 1);
+            assert_eq!(unlocked_amount, window[0].cumulative_amount);
+        }
+
+        let unlocked_amount = schedule.get_max_unlocked_amount(u64::MAX);
+        assert_eq!(unlocked_amount, schedule.get_total_deposit_amount());
+        assert_eq!(unlocked_amount, cumulative_amount);
+        }
+    }
+
+    #[test]
+    fn test_validate_points() {
+        let point = |timestamp, cumulative_amount| SchedulePoint {
+            timestamp,
+            cumulative_amount,
+        };
+
+        assert!(VestingSchedule::validate_points(&[point(10, 100), point(20, 100)]).is_ok());
+        assert_eq!(
+            VestingSchedule::validate_points(&[]).unwrap_err(),
+            LockerError::InvalidSchedulePointCount.into()
+        );
+        assert_eq!(
+            VestingSchedule::validate_points(&[point(0, 0); MAX_SCHEDULE_POINTS + 1]).unwrap_err(),
+            LockerError::InvalidSchedulePointCount.into()
+        );
+        assert_eq!(
+            VestingSchedule::validate_points(&[point(10, 100), point(10, 200)]).unwrap_err(),
+            LockerError::InvalidScheduleTimestamp.into()
+        );
+        assert_eq!(
+            VestingSchedule::validate_points(&[point(10, 200), point(20, 100)]).unwrap_err(),
+            LockerError::InvalidScheduleAmount.into()
+        );
+    }
+}
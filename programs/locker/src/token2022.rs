@@ -0,0 +1,92 @@
+//! Token-2022 helpers
+
+use crate::errors::LockerError;
+use crate::safe_math::SafeMath;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::DefaultAccountState,
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        transfer_hook, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+    state::AccountState,
+};
+use anchor_spl::token_interface::Mint;
+
+/// Reject mints with extensions that let tokens leave the escrow outside of the
+/// vesting schedule, or that stop the recipient from ever receiving them.
+pub fn validate_mint(token_mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info = token_mint.to_account_info();
+    if *mint_info.owner == Token::id() {
+        return Ok(());
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    for extension in mint.get_extension_types()? {
+        match extension {
+            // the delegate can move tokens out of the escrow at any time
+            ExtensionType::PermanentDelegate => {
+                return Err(LockerError::UnsupportedMint.into());
+            }
+            // the escrow could never pay out
+            ExtensionType::NonTransferable => {
+                return Err(LockerError::UnsupportedMint.into());
+            }
+            // the escrow token account would be created frozen
+            ExtensionType::DefaultAccountState => {
+                let default_account_state = mint.get_extension::<DefaultAccountState>()?;
+                if default_account_state.state == AccountState::Frozen as u8 {
+                    return Err(LockerError::UnsupportedMint.into());
+                }
+            }
+            // transfers are made without the extra accounts a hook program needs
+            ExtensionType::TransferHook if transfer_hook::get_program_id(&mint).is_some() => {
+                return Err(LockerError::UnsupportedMint.into());
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Amount to send so that `post_fee_amount` lands in the destination account.
+pub fn calculate_transfer_fee_included_amount(
+    token_mint: &InterfaceAccount<Mint>,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    let mint_info = token_mint.to_account_info();
+    if *mint_info.owner == Token::id() {
+        return Ok(post_fee_amount);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
+        let epoch = Clock::get()?.epoch;
+        let transfer_fee = transfer_fee_config
+            .calculate_inverse_epoch_fee(epoch, post_fee_amount)
+            .ok_or(LockerError::MathOverflow)?;
+        return Ok(post_fee_amount.safe_add(transfer_fee)?);
+    }
+    Ok(post_fee_amount)
+}
+
+/// Transfer fees withheld in a token account, which must be harvested before it can be closed.
+pub fn get_withheld_amount(token_account_info: &AccountInfo) -> Result<u64> {
+    if *token_account_info.owner == Token::id() {
+        return Ok(0);
+    }
+
+    let account_data = token_account_info.try_borrow_data()?;
+    let account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+
+    if let Ok(transfer_fee_amount) = account.get_extension::<TransferFeeAmount>() {
+        return Ok(transfer_fee_amount.withheld_amount.into());
+    }
+    Ok(0)
+}
@@ -0,0 +1,111 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use locker::{LockerError, VestingEscrow};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+struct Escrow {
+    escrow: Pubkey,
+    token_mint: Pubkey,
+    recipient: Keypair,
+}
+
+impl Escrow {
+    /// Escrow created by the payer with a cliff at `cliff_time`, and the recipient's ATA.
+    async fn new(ctx: &mut ProgramTestContext, cliff_time: u64, cancel_mode: u8) -> Self {
+        let recipient = Keypair::new();
+        let mut params = escrow_params(cliff_time, 100);
+        params.cancel_mode = cancel_mode;
+        let (escrow, token_mint) = create_escrow(ctx, params, &recipient.pubkey()).await;
+        create_token_account(ctx, &recipient.pubkey(), &token_mint, 0).await;
+        Self {
+            escrow,
+            token_mint,
+            recipient,
+        }
+    }
+
+    async fn cancel(
+        &self,
+        ctx: &mut ProgramTestContext,
+        signer: &Keypair,
+    ) -> std::result::Result<(), solana_program_test::BanksClientError> {
+        let instruction = cancel_escrow(
+            self.escrow,
+            self.token_mint,
+            ctx.payer.pubkey(),
+            self.recipient.pubkey(),
+            signer.pubkey(),
+        );
+        process(ctx, &[instruction], &[signer]).await
+    }
+}
+
+#[tokio::test]
+async fn test_cancel_vesting_escrow() {
+    let mut ctx = start().await;
+    let cliff_time = get_unix_timestamp(&mut ctx).await;
+    let escrow = Escrow::new(&mut ctx, cliff_time, 1).await;
+    advance_clock(&mut ctx, 4).await;
+    let current_ts = get_unix_timestamp(&mut ctx).await;
+
+    let creator = ctx.payer.insecure_clone();
+    escrow.cancel(&mut ctx, &creator).await.unwrap();
+
+    // the unlocked periods go to the recipient, the rest back to the creator
+    let claimable_amount = (current_ts - cliff_time).min(10) * 100;
+    let recipient_token =
+        get_associated_token_address(&escrow.recipient.pubkey(), &escrow.token_mint);
+    let creator_token = get_associated_token_address(&creator.pubkey(), &escrow.token_mint);
+    let escrow_token = get_associated_token_address(&escrow.escrow, &escrow.token_mint);
+    assert!(claimable_amount > 0 && claimable_amount < 1_000);
+    assert_eq!(
+        get_token_amount(&mut ctx, &recipient_token).await,
+        claimable_amount
+    );
+    assert_eq!(
+        get_token_amount(&mut ctx, &creator_token).await,
+        1_000 - claimable_amount
+    );
+    assert_eq!(get_token_amount(&mut ctx, &escrow_token).await, 0);
+
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow.escrow).await;
+    assert!(vesting_escrow.is_cancelled());
+    assert_eq!(vesting_escrow.cancelled_at, current_ts);
+    assert_eq!(vesting_escrow.total_claimed_amount, claimable_amount);
+
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let result = escrow.cancel(&mut ctx, &creator).await;
+    assert_locker_error(result, LockerError::AlreadyCancelled);
+}
+
+#[tokio::test]
+async fn test_cancel_vesting_escrow_cancel_mode() {
+    let mut ctx = start().await;
+    let creator = ctx.payer.insecure_clone();
+    // (cancel_mode, creator may cancel, recipient may cancel)
+    for (cancel_mode, allow_creator, allow_recipient) in [
+        (0, false, false),
+        (1, true, false),
+        (2, false, true),
+        (3, true, true),
+    ] {
+        for (by_creator, allowed) in [(true, allow_creator), (false, allow_recipient)] {
+            let escrow = Escrow::new(&mut ctx, 0, cancel_mode).await;
+            let signer = if by_creator {
+                &creator
+            } else {
+                &escrow.recipient
+            };
+            let result = escrow.cancel(&mut ctx, signer).await;
+            if allowed {
+                result.unwrap();
+            } else {
+                assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+            }
+            let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow.escrow).await;
+            assert_eq!(vesting_escrow.is_cancelled(), allowed);
+        }
+    }
+}
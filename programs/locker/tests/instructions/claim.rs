@@ -0,0 +1,141 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use locker::{LockerError, VestingEscrow};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn claim(
+    escrow: Pubkey,
+    token_mint: Pubkey,
+    recipient: Pubkey,
+    recipient_token: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::ClaimCtx {
+            escrow,
+            vesting_schedule: None,
+            token_mint,
+            escrow_token: get_associated_token_address(&escrow, &token_mint),
+            recipient,
+            recipient_token,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: System::id(),
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::Claim {
+            max_amount: u64::MAX,
+        }
+        .data(),
+    }
+}
+
+/// Fully unlocked escrow for a recipient who can pay for their own token account.
+async fn setup(ctx: &mut ProgramTestContext) -> (Pubkey, Pubkey, Keypair) {
+    let recipient = Keypair::new();
+    let (escrow, token_mint) = create_escrow(ctx, escrow_params(0, 100), &recipient.pubkey()).await;
+    let payer = ctx.payer.pubkey();
+    let transfer = system_instruction::transfer(&payer, &recipient.pubkey(), LAMPORTS_PER_SOL);
+    process(ctx, &[transfer], &[]).await.unwrap();
+    (escrow, token_mint, recipient)
+}
+
+#[tokio::test]
+async fn test_claim_creates_recipient_token() {
+    let mut ctx = start().await;
+    let (escrow, token_mint, recipient) = setup(&mut ctx).await;
+    let recipient_token = get_associated_token_address(&recipient.pubkey(), &token_mint);
+    assert!(!account_exists(&mut ctx, &recipient_token).await);
+
+    let instruction = claim(escrow, token_mint, recipient.pubkey(), recipient_token);
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+
+    assert_eq!(get_token_amount(&mut ctx, &recipient_token).await, 1_000);
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.total_claimed_amount, 1_000);
+}
+
+#[tokio::test]
+async fn test_claim_invalid_recipient_token() {
+    let mut ctx = start().await;
+    let (escrow, token_mint, recipient) = setup(&mut ctx).await;
+
+    // the recipient's ATA of another mint
+    let other_mint = create_mint(&mut ctx).await;
+    let other_token = create_token_account(&mut ctx, &recipient.pubkey(), &other_mint, 0).await;
+    let instruction = claim(escrow, token_mint, recipient.pubkey(), other_token);
+    let result = process(&mut ctx, &[instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::InvalidRecipientTokenMint);
+
+    // a token account of the escrow mint owned by someone else
+    let other_token = create_token_account(&mut ctx, &Pubkey::new_unique(), &token_mint, 0).await;
+    let instruction = claim(escrow, token_mint, recipient.pubkey(), other_token);
+    let result = process(&mut ctx, &[instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::InvalidRecipientTokenAccount);
+
+    // a missing account that is not the recipient's ATA cannot be created
+    let instruction = claim(escrow, token_mint, recipient.pubkey(), Pubkey::new_unique());
+    let result = process(&mut ctx, &[instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::InvalidRecipientTokenAccount);
+
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.total_claimed_amount, 0);
+}
+
+#[tokio::test]
+async fn test_claim_to_other_recipient_token() {
+    let mut ctx = start().await;
+    let (escrow, token_mint, recipient) = setup(&mut ctx).await;
+
+    // a token account of the recipient that is not their ATA
+    let token_account = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &token_account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(
+            &spl_token::ID,
+            &token_account.pubkey(),
+            &token_mint,
+            &recipient.pubkey(),
+        )
+        .unwrap(),
+    ];
+    process(&mut ctx, &instructions, &[&token_account])
+        .await
+        .unwrap();
+    let instruction = claim(
+        escrow,
+        token_mint,
+        recipient.pubkey(),
+        token_account.pubkey(),
+    );
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_amount(&mut ctx, &token_account.pubkey()).await,
+        1_000
+    );
+    let recipient_token = get_associated_token_address(&recipient.pubkey(), &token_mint);
+    assert!(!account_exists(&mut ctx, &recipient_token).await);
+}
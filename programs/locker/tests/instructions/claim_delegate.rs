@@ -0,0 +1,175 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use locker::{LockerError, VestingEscrow};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_claim_delegate(escrow: Pubkey, recipient: Pubkey, claim_delegate: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::SetClaimDelegateCtx {
+            escrow,
+            recipient,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::SetClaimDelegate { claim_delegate }.data(),
+    }
+}
+
+fn revoke_claim_delegate(escrow: Pubkey, recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::RevokeClaimDelegateCtx {
+            escrow,
+            recipient,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::RevokeClaimDelegate {}.data(),
+    }
+}
+
+fn claim_as_delegate(
+    escrow: Pubkey,
+    token_mint: Pubkey,
+    claim_delegate: Pubkey,
+    recipient: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::ClaimAsDelegateCtx {
+            escrow,
+            vesting_schedule: None,
+            token_mint,
+            escrow_token: get_associated_token_address(&escrow, &token_mint),
+            claim_delegate,
+            recipient_token: get_associated_token_address(&recipient, &token_mint),
+            token_program: spl_token::ID,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::ClaimAsDelegate {
+            max_amount: u64::MAX,
+        }
+        .data(),
+    }
+}
+
+/// Fully unlocked escrow with the recipient's ATA, whose recipient changes after `delay`.
+async fn setup(ctx: &mut ProgramTestContext, delay: u64) -> (Pubkey, Pubkey, Keypair) {
+    let recipient = Keypair::new();
+    let mut params = escrow_params(0, 100);
+    params.recipient_change_delay = delay;
+    let (escrow, token_mint) = create_escrow(ctx, params, &recipient.pubkey()).await;
+    create_token_account(ctx, &recipient.pubkey(), &token_mint, 0).await;
+    (escrow, token_mint, recipient)
+}
+
+#[tokio::test]
+async fn test_claim_as_delegate() {
+    let mut ctx = start().await;
+    let (escrow, token_mint, recipient) = setup(&mut ctx, 0).await;
+    let claim_delegate = Keypair::new();
+
+    // only the recipient can set a delegate
+    let instruction = set_claim_delegate(escrow, claim_delegate.pubkey(), claim_delegate.pubkey());
+    let result = process(&mut ctx, &[instruction], &[&claim_delegate]).await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    let instruction = set_claim_delegate(escrow, recipient.pubkey(), claim_delegate.pubkey());
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+
+    let wrong_signer = Keypair::new();
+    let instruction = claim_as_delegate(
+        escrow,
+        token_mint,
+        wrong_signer.pubkey(),
+        recipient.pubkey(),
+    );
+    let result = process(&mut ctx, &[instruction], &[&wrong_signer]).await;
+    assert_locker_error(result, LockerError::InvalidClaimDelegate);
+
+    let instruction = claim_as_delegate(
+        escrow,
+        token_mint,
+        claim_delegate.pubkey(),
+        recipient.pubkey(),
+    );
+    process(
+        &mut ctx,
+        std::slice::from_ref(&instruction),
+        &[&claim_delegate],
+    )
+    .await
+    .unwrap();
+    let recipient_token = get_associated_token_address(&recipient.pubkey(), &token_mint);
+    assert_eq!(get_token_amount(&mut ctx, &recipient_token).await, 1_000);
+
+    let revoke_instruction = revoke_claim_delegate(escrow, recipient.pubkey());
+    process(
+        &mut ctx,
+        std::slice::from_ref(&revoke_instruction),
+        &[&recipient],
+    )
+    .await
+    .unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert!(!vesting_escrow.has_claim_delegate());
+
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let result = process(&mut ctx, &[revoke_instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::ClaimDelegateNotSet);
+    let result = process(&mut ctx, &[instruction], &[&claim_delegate]).await;
+    assert_locker_error(result, LockerError::InvalidClaimDelegate);
+}
+
+#[tokio::test]
+async fn test_claim_delegate_with_scheduled_change() {
+    let mut ctx = start().await;
+    let delay = 10;
+    let (escrow, _, recipient) = setup(&mut ctx, delay).await;
+    let new_recipient = Keypair::new();
+    let claim_delegate = Pubkey::new_unique();
+    let creator = ctx.payer.pubkey();
+
+    let instruction = set_claim_delegate(escrow, recipient.pubkey(), claim_delegate);
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+    let instruction = update_recipient(escrow, None, creator, new_recipient.pubkey(), None);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    // the scheduled recipient has no say until the change is due
+    let instruction = set_claim_delegate(escrow, new_recipient.pubkey(), claim_delegate);
+    let result = process(
+        &mut ctx,
+        std::slice::from_ref(&instruction),
+        &[&new_recipient],
+    )
+    .await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    // and once it is, the old recipient has none left
+    advance_clock(&mut ctx, delay).await;
+    let revoke_instruction = revoke_claim_delegate(escrow, recipient.pubkey());
+    let result = process(&mut ctx, &[revoke_instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    process(&mut ctx, &[instruction], &[&new_recipient])
+        .await
+        .unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, new_recipient.pubkey());
+    assert!(!vesting_escrow.has_pending_recipient());
+    assert_eq!(vesting_escrow.claim_delegate, claim_delegate);
+}
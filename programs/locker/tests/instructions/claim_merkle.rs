@@ -0,0 +1,230 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use locker::merkle::{hash_intermediate, hash_leaf};
+use locker::{
+    CreateMerkleVestingDistributorParameters, LockerError, MerkleClaimStatus,
+    MerkleVestingDistributor,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+
+const ALICE_AMOUNT: u64 = 1_000;
+const BOB_AMOUNT: u64 = 2_000;
+
+/// Distributor for two recipients whose allocations are fully unlocked at the cliff.
+struct Distributor {
+    distributor: Pubkey,
+    token_mint: Pubkey,
+    alice: Keypair,
+    bob: Keypair,
+    root: [u8; 32],
+}
+
+impl Distributor {
+    async fn new(ctx: &mut ProgramTestContext) -> Self {
+        let alice = Keypair::new();
+        let bob = Keypair::new();
+        let root = hash_intermediate(
+            &hash_leaf(&alice.pubkey(), ALICE_AMOUNT),
+            &hash_leaf(&bob.pubkey(), BOB_AMOUNT),
+        );
+
+        let base = Keypair::new();
+        let distributor = Pubkey::find_program_address(
+            &[b"merkle_distributor".as_ref(), base.pubkey().as_ref()],
+            &locker::ID,
+        )
+        .0;
+        let token_mint = create_mint(ctx).await;
+        let payer = ctx.payer.pubkey();
+        let sender_token =
+            create_token_account(ctx, &payer, &token_mint, ALICE_AMOUNT + BOB_AMOUNT).await;
+        let vault = create_token_account(ctx, &distributor, &token_mint, 0).await;
+        for recipient in [&alice, &bob] {
+            create_token_account(ctx, &recipient.pubkey(), &token_mint, 0).await;
+            let transfer =
+                system_instruction::transfer(&payer, &recipient.pubkey(), LAMPORTS_PER_SOL);
+            process(ctx, &[transfer], &[]).await.unwrap();
+        }
+
+        let instruction = Instruction {
+            program_id: locker::ID,
+            accounts: locker::accounts::CreateMerkleVestingDistributorCtx {
+                base: base.pubkey(),
+                distributor,
+                token_mint,
+                vault,
+                sender: payer,
+                sender_token,
+                token_program: spl_token::ID,
+                system_program: System::id(),
+                event_authority: get_event_authority(),
+                program: locker::ID,
+            }
+            .to_account_metas(None),
+            data: locker::instruction::CreateMerkleVestingDistributor {
+                params: CreateMerkleVestingDistributorParameters {
+                    root,
+                    total_amount: ALICE_AMOUNT + BOB_AMOUNT,
+                    vesting_start_time: 0,
+                    cliff_time: 0,
+                    frequency: 1,
+                    number_of_period: 0,
+                    cliff_unlock_bps: 10_000,
+                    schedule_kind: 0,
+                },
+            }
+            .data(),
+        };
+        process(ctx, &[instruction], &[&base]).await.unwrap();
+
+        Self {
+            distributor,
+            token_mint,
+            alice,
+            bob,
+            root,
+        }
+    }
+
+    fn claim_status(&self, recipient: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"merkle_claim_status".as_ref(),
+                self.distributor.as_ref(),
+                recipient.as_ref(),
+            ],
+            &locker::ID,
+        )
+        .0
+    }
+
+    async fn claim(
+        &self,
+        ctx: &mut ProgramTestContext,
+        recipient: &Keypair,
+        total_amount: u64,
+        proof: Vec<[u8; 32]>,
+        max_amount: u64,
+    ) -> std::result::Result<(), solana_program_test::BanksClientError> {
+        let instruction = Instruction {
+            program_id: locker::ID,
+            accounts: locker::accounts::ClaimMerkleCtx {
+                distributor: self.distributor,
+                claim_status: self.claim_status(&recipient.pubkey()),
+                token_mint: self.token_mint,
+                vault: get_associated_token_address(&self.distributor, &self.token_mint),
+                recipient: recipient.pubkey(),
+                recipient_token: get_associated_token_address(
+                    &recipient.pubkey(),
+                    &self.token_mint,
+                ),
+                token_program: spl_token::ID,
+                system_program: System::id(),
+                event_authority: get_event_authority(),
+                program: locker::ID,
+            }
+            .to_account_metas(None),
+            data: locker::instruction::ClaimMerkle {
+                total_amount,
+                proof,
+                max_amount,
+            }
+            .data(),
+        };
+        process(ctx, &[instruction], &[recipient]).await
+    }
+}
+
+#[tokio::test]
+async fn test_claim_merkle() {
+    let mut ctx = start().await;
+    let distributor = Distributor::new(&mut ctx).await;
+    let vesting_distributor: MerkleVestingDistributor =
+        get_account(&mut ctx, &distributor.distributor).await;
+    assert_eq!(vesting_distributor.root, distributor.root);
+    let vault = get_associated_token_address(&distributor.distributor, &distributor.token_mint);
+    assert_eq!(
+        get_token_amount(&mut ctx, &vault).await,
+        ALICE_AMOUNT + BOB_AMOUNT
+    );
+
+    let alice = &distributor.alice;
+    let proof = vec![hash_leaf(&distributor.bob.pubkey(), BOB_AMOUNT)];
+    distributor
+        .claim(&mut ctx, alice, ALICE_AMOUNT, proof.clone(), 400)
+        .await
+        .unwrap();
+    distributor
+        .claim(&mut ctx, alice, ALICE_AMOUNT, proof, u64::MAX)
+        .await
+        .unwrap();
+
+    let alice_token = get_associated_token_address(&alice.pubkey(), &distributor.token_mint);
+    assert_eq!(get_token_amount(&mut ctx, &alice_token).await, ALICE_AMOUNT);
+    assert_eq!(get_token_amount(&mut ctx, &vault).await, BOB_AMOUNT);
+    let claim_status: MerkleClaimStatus =
+        get_account(&mut ctx, &distributor.claim_status(&alice.pubkey())).await;
+    assert_eq!(claim_status.total_amount, ALICE_AMOUNT);
+    assert_eq!(claim_status.total_claimed_amount, ALICE_AMOUNT);
+}
+
+#[tokio::test]
+async fn test_claim_merkle_invalid_proof() {
+    let mut ctx = start().await;
+    let distributor = Distributor::new(&mut ctx).await;
+    let alice = &distributor.alice;
+    let proof = vec![hash_leaf(&distributor.bob.pubkey(), BOB_AMOUNT)];
+
+    // an amount other than the one in the leaf
+    let result = distributor
+        .claim(&mut ctx, alice, ALICE_AMOUNT + 1, proof.clone(), u64::MAX)
+        .await;
+    assert_locker_error(result, LockerError::InvalidMerkleProof);
+
+    // a proof that does not lead to the root
+    let result = distributor
+        .claim(&mut ctx, alice, ALICE_AMOUNT, vec![[1; 32]], u64::MAX)
+        .await;
+    assert_locker_error(result, LockerError::InvalidMerkleProof);
+
+    // another recipient's proof
+    let bob = &distributor.bob;
+    let result = distributor
+        .claim(&mut ctx, bob, ALICE_AMOUNT, proof, u64::MAX)
+        .await;
+    assert_locker_error(result, LockerError::InvalidMerkleProof);
+
+    let vault = get_associated_token_address(&distributor.distributor, &distributor.token_mint);
+    assert_eq!(
+        get_token_amount(&mut ctx, &vault).await,
+        ALICE_AMOUNT + BOB_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn test_claim_merkle_twice() {
+    let mut ctx = start().await;
+    let distributor = Distributor::new(&mut ctx).await;
+    let bob = &distributor.bob;
+    let proof = vec![hash_leaf(&distributor.alice.pubkey(), ALICE_AMOUNT)];
+
+    distributor
+        .claim(&mut ctx, bob, BOB_AMOUNT, proof.clone(), u64::MAX)
+        .await
+        .unwrap();
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    let result = distributor
+        .claim(&mut ctx, bob, BOB_AMOUNT, proof, u64::MAX)
+        .await;
+    assert_locker_error(result, LockerError::MerkleAllocationFullyClaimed);
+
+    let bob_token = get_associated_token_address(&bob.pubkey(), &distributor.token_mint);
+    assert_eq!(get_token_amount(&mut ctx, &bob_token).await, BOB_AMOUNT);
+}
@@ -0,0 +1,56 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token;
+use locker::{LockerError, VestingEscrow};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn test_create_vesting_escrow_existing_escrow_token() {
+    let mut ctx = start().await;
+    let token_mint = create_mint(&mut ctx).await;
+    let payer = ctx.payer.pubkey();
+    let sender_token = create_token_account(&mut ctx, &payer, &token_mint, 10_000).await;
+    let recipient = Pubkey::new_unique();
+
+    // an existing, empty escrow ATA is fine
+    let base = Keypair::new();
+    let escrow = get_escrow(&base.pubkey());
+    let escrow_token = create_token_account(&mut ctx, &escrow, &token_mint, 0).await;
+    let instruction = create_escrow_instruction(
+        &ctx,
+        &base.pubkey(),
+        &token_mint,
+        escrow_params(0, 100),
+        &recipient,
+    );
+    process(&mut ctx, &[instruction], &[&base]).await.unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, recipient);
+    assert_eq!(get_token_amount(&mut ctx, &escrow_token).await, 1_000);
+
+    // one that already holds tokens is rejected
+    let base = Keypair::new();
+    let escrow = get_escrow(&base.pubkey());
+    let escrow_token = create_token_account(&mut ctx, &escrow, &token_mint, 0).await;
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &sender_token,
+        &escrow_token,
+        &payer,
+        &[],
+        1,
+    )
+    .unwrap();
+    process(&mut ctx, &[transfer], &[]).await.unwrap();
+
+    let instruction = create_escrow_instruction(
+        &ctx,
+        &base.pubkey(),
+        &token_mint,
+        escrow_params(0, 100),
+        &recipient,
+    );
+    let result = process(&mut ctx, &[instruction], &[&base]).await;
+    assert_locker_error(result, LockerError::EscrowTokenNotEmpty);
+    assert!(!account_exists(&mut ctx, &escrow).await);
+}
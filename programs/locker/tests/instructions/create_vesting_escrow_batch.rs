@@ -0,0 +1,169 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use locker::{get_batch_base, CreateVestingEscrowParameters, LockerError, VestingEscrow};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+struct Batch {
+    base: Keypair,
+    token_mint: Pubkey,
+    sender_token: Pubkey,
+    /// `(recipient, escrow, escrow_token)` of each entry
+    entries: Vec<(Pubkey, Pubkey, Pubkey)>,
+}
+
+impl Batch {
+    async fn new(ctx: &mut ProgramTestContext, len: u64) -> Self {
+        let base = Keypair::new();
+        let token_mint = create_mint(ctx).await;
+        let sender_token =
+            create_token_account(ctx, &ctx.payer.pubkey(), &token_mint, 1_000_000).await;
+        let entries = (0..len)
+            .map(|index| {
+                let escrow = get_escrow(&get_batch_base(&base.pubkey(), index));
+                let escrow_token = get_associated_token_address(&escrow, &token_mint);
+                (Pubkey::new_unique(), escrow, escrow_token)
+            })
+            .collect();
+        Self {
+            base,
+            token_mint,
+            sender_token,
+            entries,
+        }
+    }
+
+    fn params(&self) -> Vec<CreateVestingEscrowParameters> {
+        (1..=self.entries.len() as u64)
+            .map(|index| escrow_params(0, 100 * index))
+            .collect()
+    }
+
+    fn instruction(&self, ctx: &ProgramTestContext) -> Instruction {
+        let mut accounts = locker::accounts::CreateVestingEscrowBatchCtx {
+            base: self.base.pubkey(),
+            token_mint: self.token_mint,
+            sender: ctx.payer.pubkey(),
+            sender_token: self.sender_token,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: System::id(),
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None);
+        for (recipient, escrow, escrow_token) in &self.entries {
+            accounts.push(AccountMeta::new_readonly(*recipient, false));
+            accounts.push(AccountMeta::new(*escrow, false));
+            accounts.push(AccountMeta::new(*escrow_token, false));
+        }
+        Instruction {
+            program_id: locker::ID,
+            accounts,
+            data: locker::instruction::CreateVestingEscrowBatch {
+                params: self.params(),
+            }
+            .data(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_create_vesting_escrow_batch() {
+    let mut ctx = start().await;
+    let batch = Batch::new(&mut ctx, 3).await;
+    // an existing, empty escrow ATA is fine
+    let (_, escrow, _) = batch.entries[1];
+    create_token_account(&mut ctx, &escrow, &batch.token_mint, 0).await;
+
+    let instruction = batch.instruction(&ctx);
+    process(&mut ctx, &[instruction], &[&batch.base])
+        .await
+        .unwrap();
+
+    for ((recipient, escrow, escrow_token), params) in batch.entries.iter().zip(batch.params()) {
+        let vesting_escrow: VestingEscrow = get_account(&mut ctx, escrow).await;
+        assert_eq!(vesting_escrow.recipient, *recipient);
+        assert_eq!(vesting_escrow.creator, ctx.payer.pubkey());
+        assert_eq!(vesting_escrow.token_mint, batch.token_mint);
+        assert_eq!(vesting_escrow.amount_per_period, params.amount_per_period);
+        assert_eq!(
+            get_token_amount(&mut ctx, escrow_token).await,
+            params.get_total_deposit_amount().unwrap()
+        );
+    }
+    assert_eq!(
+        get_token_amount(&mut ctx, &batch.sender_token).await,
+        1_000_000 - (1_000 + 2_000 + 3_000)
+    );
+}
+
+#[tokio::test]
+async fn test_create_vesting_escrow_batch_invalid_accounts() {
+    let mut ctx = start().await;
+    let batch = Batch::new(&mut ctx, 2).await;
+
+    // one account short of the last triple
+    let mut instruction = batch.instruction(&ctx);
+    instruction.accounts.pop();
+    let result = process(&mut ctx, &[instruction], &[&batch.base]).await;
+    assert_locker_error(result, LockerError::InvalidBatchAccounts);
+
+    // one params entry more than there are triples
+    let mut instruction = batch.instruction(&ctx);
+    let mut params = batch.params();
+    params.push(escrow_params(0, 1));
+    instruction.data = locker::instruction::CreateVestingEscrowBatch { params }.data();
+    let result = process(&mut ctx, &[instruction], &[&batch.base]).await;
+    assert_locker_error(result, LockerError::InvalidBatchAccounts);
+
+    // escrows passed out of order
+    let mut instruction = batch.instruction(&ctx);
+    let len = instruction.accounts.len();
+    instruction.accounts.swap(len - 5, len - 2);
+    let result = process(&mut ctx, &[instruction], &[&batch.base]).await;
+    assert_locker_error(result, LockerError::InvalidEscrowAddress);
+
+    // escrow token account that is not the escrow ATA
+    let mut instruction = batch.instruction(&ctx);
+    let last = instruction.accounts.len() - 1;
+    instruction.accounts[last].pubkey = Pubkey::new_unique();
+    let result = process(&mut ctx, &[instruction], &[&batch.base]).await;
+    assert_locker_error(result, LockerError::InvalidEscrowTokenAddress);
+
+    for (_, escrow, _) in &batch.entries {
+        assert!(!account_exists(&mut ctx, escrow).await);
+    }
+}
+
+#[tokio::test]
+async fn test_create_vesting_escrow_batch_stray_balance() {
+    let mut ctx = start().await;
+    let batch = Batch::new(&mut ctx, 2).await;
+    let (_, escrow, _) = batch.entries[1];
+    create_token_account(&mut ctx, &escrow, &batch.token_mint, 0).await;
+    let (_, _, escrow_token) = batch.entries[1];
+    let payer = ctx.payer.pubkey();
+    let transfer = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &batch.sender_token,
+        &escrow_token,
+        &payer,
+        &[],
+        1,
+    )
+    .unwrap();
+    process(&mut ctx, &[transfer], &[]).await.unwrap();
+
+    let instruction = batch.instruction(&ctx);
+    let result = process(&mut ctx, &[instruction], &[&batch.base]).await;
+    assert_locker_error(result, LockerError::EscrowTokenNotEmpty);
+
+    // the whole batch fails, including the valid first entry
+    let (_, escrow, _) = batch.entries[0];
+    assert!(!account_exists(&mut ctx, &escrow).await);
+}
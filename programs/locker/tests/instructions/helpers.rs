@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{instruction::Instruction, system_instruction};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account;
+use anchor_spl::token::spl_token;
+use locker::{CreateVestingEscrowParameters, LockerError};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+fn process_locker_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // the anchor entrypoint ties the account slice to the accounts' own lifetime
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    locker::entry(program_id, accounts, data)
+}
+
+pub async fn start() -> ProgramTestContext {
+    let program_test =
+        ProgramTest::new("locker", locker::ID, processor!(process_locker_instruction));
+    program_test.start_with_context().await
+}
+
+pub async fn process(
+    ctx: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&ctx.payer.pubkey()),
+        &all_signers,
+        ctx.last_blockhash,
+    );
+    ctx.banks_client.process_transaction(transaction).await
+}
+
+pub fn assert_locker_error(result: std::result::Result<(), BanksClientError>, error: LockerError) {
+    match result.expect_err("transaction succeeded").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error), "expected {error:?}")
+        }
+        err => panic!("expected {error:?}, got {err:?}"),
+    }
+}
+
+pub fn get_event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &locker::ID).0
+}
+
+pub fn get_escrow(base: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow".as_ref(), base.as_ref()], &locker::ID).0
+}
+
+pub fn get_escrow_metadata(escrow: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow_metadata".as_ref(), escrow.as_ref()], &locker::ID).0
+}
+
+pub async fn get_account<T: AccountDeserialize>(ctx: &mut ProgramTestContext, key: &Pubkey) -> T {
+    let account = ctx.banks_client.get_account(*key).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn account_exists(ctx: &mut ProgramTestContext, key: &Pubkey) -> bool {
+    ctx.banks_client.get_account(*key).await.unwrap().is_some()
+}
+
+pub async fn get_token_amount(ctx: &mut ProgramTestContext, token_account: &Pubkey) -> u64 {
+    let account = ctx
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+/// SPL token mint with the payer as mint authority.
+pub async fn create_mint(ctx: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = ctx.payer.pubkey();
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &payer, None, 6)
+            .unwrap(),
+    ];
+    process(ctx, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create the ATA of `owner` for `mint` and mint `amount` to it.
+pub async fn create_token_account(
+    ctx: &mut ProgramTestContext,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let payer = ctx.payer.pubkey();
+    let token_account = get_associated_token_address(owner, mint);
+    let mut instructions = vec![create_associated_token_account(
+        &payer,
+        owner,
+        mint,
+        &spl_token::ID,
+    )];
+    if amount > 0 {
+        instructions.push(
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                mint,
+                &token_account,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        );
+    }
+    process(ctx, &instructions, &[]).await.unwrap();
+    token_account
+}
+
+/// Periodic escrow unlocking `amount_per_period` every second after a cliff at `cliff_time`.
+pub fn escrow_params(cliff_time: u64, amount_per_period: u64) -> CreateVestingEscrowParameters {
+    CreateVestingEscrowParameters {
+        vesting_start_time: 0,
+        cliff_time,
+        frequency: 1,
+        cliff_unlock_amount: 0,
+        amount_per_period,
+        number_of_period: 10,
+        update_recipient_mode: 3,
+        cancel_mode: 0,
+        schedule_kind: 0,
+        time_unit: 0,
+        allow_crank_claim: false,
+        recipient_change_delay: 0,
+    }
+}
+
+pub fn create_escrow_instruction(
+    ctx: &ProgramTestContext,
+    base: &Pubkey,
+    token_mint: &Pubkey,
+    params: CreateVestingEscrowParameters,
+    recipient: &Pubkey,
+) -> Instruction {
+    let escrow = get_escrow(base);
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::CreateVestingEscrowCtx {
+            base: *base,
+            escrow,
+            token_mint: *token_mint,
+            escrow_token: get_associated_token_address(&escrow, token_mint),
+            sender: ctx.payer.pubkey(),
+            sender_token: get_associated_token_address(&ctx.payer.pubkey(), token_mint),
+            recipient: *recipient,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: System::id(),
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::CreateVestingEscrow { params }.data(),
+    }
+}
+
+/// Create an escrow for `recipient` funded by the payer, returning the escrow and its mint.
+pub async fn create_escrow(
+    ctx: &mut ProgramTestContext,
+    params: CreateVestingEscrowParameters,
+    recipient: &Pubkey,
+) -> (Pubkey, Pubkey) {
+    let base = Keypair::new();
+    let token_mint = create_mint(ctx).await;
+    let amount = params.get_total_deposit_amount().unwrap();
+    create_token_account(ctx, &ctx.payer.pubkey(), &token_mint, amount).await;
+    let instruction =
+        create_escrow_instruction(ctx, &base.pubkey(), &token_mint, params, recipient);
+    process(ctx, &[instruction], &[&base]).await.unwrap();
+    (get_escrow(&base.pubkey()), token_mint)
+}
+
+pub fn update_recipient(
+    escrow: Pubkey,
+    escrow_metadata: Option<Pubkey>,
+    signer: Pubkey,
+    new_recipient: Pubkey,
+    new_recipient_email: Option<String>,
+) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::UpdateVestingEscrowRecipientCtx {
+            escrow,
+            escrow_metadata,
+            signer,
+            system_program: System::id(),
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::UpdateVestingEscrowRecipient {
+            new_recipient,
+            new_recipient_email,
+        }
+        .data(),
+    }
+}
+
+pub fn cancel_escrow(
+    escrow: Pubkey,
+    token_mint: Pubkey,
+    creator: Pubkey,
+    recipient: Pubkey,
+    signer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::CancelVestingEscrowCtx {
+            escrow,
+            vesting_schedule: None,
+            token_mint,
+            escrow_token: get_associated_token_address(&escrow, &token_mint),
+            creator_token: get_associated_token_address(&creator, &token_mint),
+            recipient_token: get_associated_token_address(&recipient, &token_mint),
+            signer,
+            token_program: spl_token::ID,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::CancelVestingEscrow {}.data(),
+    }
+}
+
+pub async fn get_unix_timestamp(ctx: &mut ProgramTestContext) -> u64 {
+    let clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp as u64
+}
+
+pub async fn advance_clock(ctx: &mut ProgramTestContext, seconds: u64) {
+    let mut clock: Clock = ctx.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += seconds as i64;
+    ctx.set_sysvar(&clock);
+    // a fresh blockhash keeps retried transactions from hitting the status cache
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+}
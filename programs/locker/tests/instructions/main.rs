@@ -0,0 +1,13 @@
+//! Instruction tests running the locker natively in `solana-program-test`, next to the
+//! SPL token and associated token programs it calls.
+
+mod cancel_vesting_escrow;
+mod claim;
+mod claim_delegate;
+mod claim_merkle;
+mod create_vesting_escrow;
+mod create_vesting_escrow_batch;
+mod helpers;
+mod migrate_vesting_escrow_metadata;
+mod recipient_change_delay;
+mod recipient_proposal;
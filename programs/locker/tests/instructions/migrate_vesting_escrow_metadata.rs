@@ -0,0 +1,87 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{Discriminator, InstructionData, ToAccountMetas};
+use locker::{LockerError, VestingEscrowMetadata};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::account::Account;
+use solana_sdk::signature::Signer;
+
+fn migrate(ctx: &ProgramTestContext, escrow: Pubkey, escrow_metadata: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::MigrateVestingEscrowMetadataCtx {
+            escrow,
+            escrow_metadata,
+            payer: ctx.payer.pubkey(),
+            system_program: System::id(),
+        }
+        .to_account_metas(None),
+        data: locker::instruction::MigrateVestingEscrowMetadata {}.data(),
+    }
+}
+
+/// Store a metadata account as it was written before `uri` and `attributes` existed.
+async fn set_legacy_metadata(ctx: &mut ProgramTestContext, escrow: &Pubkey) -> Pubkey {
+    let escrow_metadata = get_escrow_metadata(escrow);
+    let mut data = VestingEscrowMetadata::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(escrow.as_ref());
+    for field in [
+        "name",
+        "description",
+        "creator@example.com",
+        "old@example.com",
+    ] {
+        field.to_owned().serialize(&mut data).unwrap();
+    }
+    let rent = ctx.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: locker::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    ctx.set_account(&escrow_metadata, &account.into());
+    escrow_metadata
+}
+
+#[tokio::test]
+async fn test_migrate_vesting_escrow_metadata() {
+    let mut ctx = start().await;
+    let (escrow, _) = create_escrow(&mut ctx, escrow_params(0, 100), &Pubkey::new_unique()).await;
+    let escrow_metadata = set_legacy_metadata(&mut ctx, &escrow).await;
+    let creator = ctx.payer.pubkey();
+
+    // legacy accounts cannot be loaded by the metadata instructions
+    let instruction = update_recipient(
+        escrow,
+        Some(escrow_metadata),
+        creator,
+        Pubkey::new_unique(),
+        Some("new@example.com".to_owned()),
+    );
+    let result = process(&mut ctx, std::slice::from_ref(&instruction), &[]).await;
+    assert!(result.is_err());
+
+    let migrate_instruction = migrate(&ctx, escrow, escrow_metadata);
+    process(&mut ctx, &[migrate_instruction], &[])
+        .await
+        .unwrap();
+
+    let metadata: VestingEscrowMetadata = get_account(&mut ctx, &escrow_metadata).await;
+    assert_eq!(metadata.escrow, escrow);
+    assert_eq!(metadata.name, "name");
+    assert_eq!(metadata.recipient_email, "old@example.com");
+    assert!(metadata.uri.is_empty());
+    assert!(metadata.attributes.is_empty());
+
+    ctx.last_blockhash = ctx.get_new_latest_blockhash().await.unwrap();
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+    let metadata: VestingEscrowMetadata = get_account(&mut ctx, &escrow_metadata).await;
+    assert_eq!(metadata.recipient_email, "new@example.com");
+
+    let migrate_instruction = migrate(&ctx, escrow, escrow_metadata);
+    let result = process(&mut ctx, &[migrate_instruction], &[]).await;
+    assert_locker_error(result, LockerError::MetadataNotLegacy);
+}
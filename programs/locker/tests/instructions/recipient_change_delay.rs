@@ -0,0 +1,173 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use locker::{
+    CreateVestingEscrowMetadataParameters, LockerError, UpdateVestingEscrowMetadataParameters,
+    VestingEscrow, VestingEscrowMetadata,
+};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+const DELAY: u64 = 10;
+
+/// Escrow created by the payer, cancellable by either party, with delayed recipient changes.
+async fn setup(ctx: &mut ProgramTestContext, recipient: &Pubkey) -> (Pubkey, Pubkey) {
+    let mut params = escrow_params(0, 100);
+    params.cancel_mode = 3;
+    params.recipient_change_delay = DELAY;
+    create_escrow(ctx, params, recipient).await
+}
+
+/// Metadata created by the payer, with `old@example.com` as the recipient email.
+async fn create_metadata(ctx: &mut ProgramTestContext, escrow: Pubkey) -> Pubkey {
+    let escrow_metadata = get_escrow_metadata(&escrow);
+    let creator = ctx.payer.pubkey();
+    let instruction = Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::CreateVestingEscrowMetadataCtx {
+            escrow,
+            creator,
+            escrow_metadata,
+            payer: creator,
+            system_program: System::id(),
+        }
+        .to_account_metas(None),
+        data: locker::instruction::CreateVestingEscrowMetadata {
+            params: CreateVestingEscrowMetadataParameters {
+                name: "name".to_owned(),
+                description: "description".to_owned(),
+                creator_email: "creator@example.com".to_owned(),
+                recipient_email: "old@example.com".to_owned(),
+            },
+        }
+        .data(),
+    };
+    process(ctx, &[instruction], &[]).await.unwrap();
+    escrow_metadata
+}
+
+fn update_recipient_email(
+    escrow: Pubkey,
+    escrow_metadata: Pubkey,
+    creator: Pubkey,
+    recipient_email: &str,
+) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::UpdateVestingEscrowMetadataCtx {
+            escrow,
+            escrow_metadata,
+            creator,
+            system_program: System::id(),
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::UpdateVestingEscrowMetadata {
+            params: UpdateVestingEscrowMetadataParameters {
+                name: None,
+                description: None,
+                creator_email: None,
+                recipient_email: Some(recipient_email.to_owned()),
+            },
+        }
+        .data(),
+    }
+}
+
+#[tokio::test]
+async fn test_cancel_applies_due_recipient_change() {
+    let mut ctx = start().await;
+    let old_recipient = Pubkey::new_unique();
+    let new_recipient = Pubkey::new_unique();
+    let (escrow, token_mint) = setup(&mut ctx, &old_recipient).await;
+    create_token_account(&mut ctx, &old_recipient, &token_mint, 0).await;
+    let new_recipient_token = create_token_account(&mut ctx, &new_recipient, &token_mint, 0).await;
+    let creator = ctx.payer.pubkey();
+
+    let instruction = update_recipient(escrow, None, creator, new_recipient, None);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    // not due yet, the unlocked amount still belongs to the old recipient
+    let instruction = cancel_escrow(escrow, token_mint, creator, new_recipient, creator);
+    let result = process(&mut ctx, &[instruction], &[]).await;
+    assert_locker_error(result, LockerError::InvalidRecipientTokenAccount);
+
+    advance_clock(&mut ctx, DELAY).await;
+    let instruction = cancel_escrow(escrow, token_mint, creator, old_recipient, creator);
+    let result = process(&mut ctx, &[instruction], &[]).await;
+    assert_locker_error(result, LockerError::InvalidRecipientTokenAccount);
+
+    let instruction = cancel_escrow(escrow, token_mint, creator, new_recipient, creator);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, new_recipient);
+    assert!(vesting_escrow.is_cancelled());
+    assert!(!vesting_escrow.has_pending_recipient());
+    assert_eq!(
+        get_token_amount(&mut ctx, &new_recipient_token).await,
+        1_000
+    );
+}
+
+#[tokio::test]
+async fn test_update_applies_due_recipient_change() {
+    let mut ctx = start().await;
+    let new_recipient = Keypair::new();
+    let (escrow, _) = setup(&mut ctx, &Pubkey::new_unique()).await;
+    let creator = ctx.payer.pubkey();
+
+    let instruction = update_recipient(escrow, None, creator, new_recipient.pubkey(), None);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    // the scheduled recipient cannot update before the change is due
+    let next_recipient = Pubkey::new_unique();
+    let instruction = update_recipient(escrow, None, new_recipient.pubkey(), next_recipient, None);
+    let result = process(
+        &mut ctx,
+        std::slice::from_ref(&instruction),
+        &[&new_recipient],
+    )
+    .await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    advance_clock(&mut ctx, DELAY).await;
+    process(&mut ctx, &[instruction], &[&new_recipient])
+        .await
+        .unwrap();
+
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, new_recipient.pubkey());
+    assert_eq!(vesting_escrow.pending_recipient, next_recipient);
+    assert!(vesting_escrow.has_scheduled_recipient());
+}
+
+#[tokio::test]
+async fn test_recipient_email_with_scheduled_change() {
+    let mut ctx = start().await;
+    let (escrow, _) = setup(&mut ctx, &Pubkey::new_unique()).await;
+    let escrow_metadata = create_metadata(&mut ctx, escrow).await;
+    let creator = ctx.payer.pubkey();
+
+    let instruction = update_recipient(
+        escrow,
+        Some(escrow_metadata),
+        creator,
+        Pubkey::new_unique(),
+        Some("new@example.com".to_owned()),
+    );
+    let result = process(&mut ctx, &[instruction], &[]).await;
+    assert_locker_error(result, LockerError::RecipientEmailWithScheduledChange);
+
+    // nor can the email be changed on its own once a change is scheduled
+    let instruction = update_recipient(escrow, None, creator, Pubkey::new_unique(), None);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+    let instruction = update_recipient_email(escrow, escrow_metadata, creator, "new@example.com");
+    let result = process(&mut ctx, &[instruction], &[]).await;
+    assert_locker_error(result, LockerError::RecipientEmailWithScheduledChange);
+
+    let metadata: VestingEscrowMetadata = get_account(&mut ctx, &escrow_metadata).await;
+    assert_eq!(metadata.recipient_email, "old@example.com");
+}
@@ -0,0 +1,195 @@
+use crate::helpers::*;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use locker::{LockerError, VestingEscrow};
+use solana_program_test::ProgramTestContext;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn propose(escrow: Pubkey, signer: Pubkey, new_recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::ProposeVestingEscrowRecipientCtx {
+            escrow,
+            signer,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::ProposeVestingEscrowRecipient { new_recipient }.data(),
+    }
+}
+
+fn accept(escrow: Pubkey, pending_recipient: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::AcceptVestingEscrowRecipientCtx {
+            escrow,
+            pending_recipient,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::AcceptVestingEscrowRecipient {}.data(),
+    }
+}
+
+fn cancel_proposal(escrow: Pubkey, signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: locker::ID,
+        accounts: locker::accounts::CancelVestingEscrowRecipientProposalCtx {
+            escrow,
+            signer,
+            event_authority: get_event_authority(),
+            program: locker::ID,
+        }
+        .to_account_metas(None),
+        data: locker::instruction::CancelVestingEscrowRecipientProposal {}.data(),
+    }
+}
+
+fn update(escrow: Pubkey, signer: Pubkey, new_recipient: Pubkey) -> Instruction {
+    update_recipient(escrow, None, signer, new_recipient, None)
+}
+
+/// Escrow created by the payer for a fresh recipient, who may also change the recipient.
+async fn setup(ctx: &mut ProgramTestContext) -> (Pubkey, Keypair) {
+    let recipient = Keypair::new();
+    let (escrow, _) = create_escrow(ctx, escrow_params(0, 100), &recipient.pubkey()).await;
+    (escrow, recipient)
+}
+
+#[tokio::test]
+async fn test_propose_and_accept_recipient() {
+    let mut ctx = start().await;
+    let (escrow, recipient) = setup(&mut ctx).await;
+    let new_recipient = Keypair::new();
+
+    let instruction = propose(escrow, recipient.pubkey(), new_recipient.pubkey());
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, recipient.pubkey());
+    assert_eq!(vesting_escrow.pending_recipient, new_recipient.pubkey());
+
+    // only the proposed recipient can accept
+    let wrong_signer = Keypair::new();
+    let instruction = accept(escrow, wrong_signer.pubkey());
+    let result = process(&mut ctx, &[instruction], &[&wrong_signer]).await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    let instruction = accept(escrow, new_recipient.pubkey());
+    process(&mut ctx, &[instruction], &[&new_recipient])
+        .await
+        .unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, new_recipient.pubkey());
+    assert!(!vesting_escrow.has_pending_recipient());
+}
+
+#[tokio::test]
+async fn test_propose_recipient_by_unpermitted_signer() {
+    let mut ctx = start().await;
+    let (escrow, _) = setup(&mut ctx).await;
+    let signer = Keypair::new();
+
+    let instruction = propose(escrow, signer.pubkey(), signer.pubkey());
+    let result = process(&mut ctx, &[instruction], &[&signer]).await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+}
+
+#[tokio::test]
+async fn test_cancel_recipient_proposal() {
+    let mut ctx = start().await;
+    let (escrow, recipient) = setup(&mut ctx).await;
+    let new_recipient = Keypair::new();
+    let creator = ctx.payer.pubkey();
+
+    let instruction = propose(escrow, creator, new_recipient.pubkey());
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+    let instruction = cancel_proposal(escrow, creator);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, recipient.pubkey());
+    assert!(!vesting_escrow.has_pending_recipient());
+
+    // the cancelled proposal can no longer be accepted
+    let instruction = accept(escrow, new_recipient.pubkey());
+    let result = process(&mut ctx, &[instruction], &[&new_recipient]).await;
+    assert_locker_error(result, LockerError::NotPermitToDoThisAction);
+
+    // and there is nothing left to cancel
+    let instruction = cancel_proposal(escrow, recipient.pubkey());
+    let result = process(&mut ctx, &[instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::NoPendingRecipient);
+}
+
+#[tokio::test]
+async fn test_update_recipient_after_proposal() {
+    let mut ctx = start().await;
+    let (escrow, recipient) = setup(&mut ctx).await;
+    let proposed = Keypair::new();
+
+    // one-step updates still work on escrows that never used a proposal
+    let instruction = update(escrow, recipient.pubkey(), recipient.pubkey());
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+
+    let instruction = propose(escrow, recipient.pubkey(), proposed.pubkey());
+    process(&mut ctx, &[instruction], &[&recipient])
+        .await
+        .unwrap();
+
+    // a one-step update can neither bypass nor leave behind a stale proposal
+    let instruction = update(escrow, recipient.pubkey(), Pubkey::new_unique());
+    let result = process(&mut ctx, &[instruction], &[&recipient]).await;
+    assert_locker_error(result, LockerError::RecipientAcceptanceRequired);
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, recipient.pubkey());
+    assert_eq!(vesting_escrow.pending_recipient, proposed.pubkey());
+
+    let instruction = accept(escrow, proposed.pubkey());
+    process(&mut ctx, &[instruction], &[&proposed])
+        .await
+        .unwrap();
+
+    // the requirement outlives the proposal that set it
+    let instruction = update(escrow, proposed.pubkey(), Pubkey::new_unique());
+    let result = process(&mut ctx, &[instruction], &[&proposed]).await;
+    assert_locker_error(result, LockerError::RecipientAcceptanceRequired);
+}
+
+#[tokio::test]
+async fn test_propose_recipient_with_scheduled_change() {
+    let mut ctx = start().await;
+    let recipient = Keypair::new();
+    let mut params = escrow_params(0, 100);
+    params.recipient_change_delay = 10;
+    let (escrow, _) = create_escrow(&mut ctx, params, &recipient.pubkey()).await;
+    let creator = ctx.payer.pubkey();
+    let scheduled = Pubkey::new_unique();
+    let proposed = Pubkey::new_unique();
+
+    let instruction = update(escrow, creator, scheduled);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+
+    // the scheduled change is not silently replaced
+    let instruction = propose(escrow, creator, proposed);
+    let result = process(&mut ctx, &[instruction], &[]).await;
+    assert_locker_error(result, LockerError::RecipientChangeScheduled);
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.pending_recipient, scheduled);
+    assert!(vesting_escrow.has_scheduled_recipient());
+
+    // once due, it is applied before the proposal
+    advance_clock(&mut ctx, 10).await;
+    let instruction = propose(escrow, creator, proposed);
+    process(&mut ctx, &[instruction], &[]).await.unwrap();
+    let vesting_escrow: VestingEscrow = get_account(&mut ctx, &escrow).await;
+    assert_eq!(vesting_escrow.recipient, scheduled);
+    assert_eq!(vesting_escrow.pending_recipient, proposed);
+    assert!(vesting_escrow.has_proposed_recipient());
+}
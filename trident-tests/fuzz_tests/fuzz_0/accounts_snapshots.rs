@@ -1,24 +1,31 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use trident_client::fuzzing::{anchor_lang, FuzzingError};
 pub struct CreateVestingEscrowSnapshot<'info> {
     pub base: Signer<'info>,
     pub escrow: Option<Account<'info, locker::state::vesting_escrow::VestingEscrow>>,
-    pub escrow_token: Account<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub escrow_token: Option<InterfaceAccount<'info, TokenAccount>>,
     pub sender: Signer<'info>,
-    pub sender_token: Account<'info, TokenAccount>,
+    pub sender_token: InterfaceAccount<'info, TokenAccount>,
     pub recipient: UncheckedAccount<'info>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub event_authority: &'info AccountInfo<'info>,
     pub program: &'info AccountInfo<'info>,
 }
 pub struct ClaimSnapshot<'info> {
     pub escrow: Account<'info, locker::state::vesting_escrow::VestingEscrow>,
-    pub escrow_token: Account<'info, TokenAccount>,
+    pub vesting_schedule: Option<Account<'info, locker::state::vesting_schedule::VestingSchedule>>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    pub escrow_token: InterfaceAccount<'info, TokenAccount>,
     pub recipient: Signer<'info>,
-    pub recipient_token: Account<'info, TokenAccount>,
-    pub token_program: Program<'info, Token>,
+    pub recipient_token: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
     pub event_authority: &'info AccountInfo<'info>,
     pub program: &'info AccountInfo<'info>,
 }
@@ -70,13 +77,34 @@ impl<'info> CreateVestingEscrowSnapshot<'info> {
             })
             .transpose()
             .unwrap_or(None);
-        let escrow_token: anchor_lang::accounts::account::Account<TokenAccount> = accounts_iter
+        let token_mint: anchor_lang::accounts::interface_account::InterfaceAccount<Mint> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("token_mint".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface_account::InterfaceAccount::try_from)
+                .ok_or(FuzzingError::AccountNotFound("token_mint".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("token_mint".to_string()))?;
+        let escrow_token: Option<
+            anchor_lang::accounts::interface_account::InterfaceAccount<TokenAccount>,
+        > = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts("escrow_token".to_string()))?
             .as_ref()
-            .map(anchor_lang::accounts::account::Account::try_from)
-            .ok_or(FuzzingError::AccountNotFound("escrow_token".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("escrow_token".to_string()))?;
+            .map(|acc| {
+                if acc.key() != *_program_id {
+                    anchor_lang::accounts::interface_account::InterfaceAccount::try_from(acc)
+                        .map_err(|_| {
+                            FuzzingError::CannotDeserializeAccount("escrow_token".to_string())
+                        })
+                } else {
+                    Err(FuzzingError::OptionalAccountNotProvided(
+                        "escrow_token".to_string(),
+                    ))
+                }
+            })
+            .transpose()
+            .unwrap_or(None);
         let sender: Signer<'_> = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts("sender".to_string()))?
@@ -84,26 +112,42 @@ impl<'info> CreateVestingEscrowSnapshot<'info> {
             .map(anchor_lang::accounts::signer::Signer::try_from)
             .ok_or(FuzzingError::AccountNotFound("sender".to_string()))?
             .map_err(|_| FuzzingError::CannotDeserializeAccount("sender".to_string()))?;
-        let sender_token: anchor_lang::accounts::account::Account<TokenAccount> = accounts_iter
-            .next()
-            .ok_or(FuzzingError::NotEnoughAccounts("sender_token".to_string()))?
-            .as_ref()
-            .map(anchor_lang::accounts::account::Account::try_from)
-            .ok_or(FuzzingError::AccountNotFound("sender_token".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("sender_token".to_string()))?;
+        let sender_token: anchor_lang::accounts::interface_account::InterfaceAccount<TokenAccount> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("sender_token".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface_account::InterfaceAccount::try_from)
+                .ok_or(FuzzingError::AccountNotFound("sender_token".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("sender_token".to_string()))?;
         let recipient = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts("recipient".to_string()))?
             .as_ref()
             .map(anchor_lang::accounts::unchecked_account::UncheckedAccount::try_from)
             .ok_or(FuzzingError::AccountNotFound("recipient".to_string()))?;
-        let token_program: anchor_lang::accounts::program::Program<Token> = accounts_iter
-            .next()
-            .ok_or(FuzzingError::NotEnoughAccounts("token_program".to_string()))?
-            .as_ref()
-            .map(anchor_lang::accounts::program::Program::try_from)
-            .ok_or(FuzzingError::AccountNotFound("token_program".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("token_program".to_string()))?;
+        let token_program: anchor_lang::accounts::interface::Interface<TokenInterface> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("token_program".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface::Interface::try_from)
+                .ok_or(FuzzingError::AccountNotFound("token_program".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("token_program".to_string()))?;
+        let associated_token_program: anchor_lang::accounts::program::Program<AssociatedToken> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts(
+                    "associated_token_program".to_string(),
+                ))?
+                .as_ref()
+                .map(anchor_lang::accounts::program::Program::try_from)
+                .ok_or(FuzzingError::AccountNotFound(
+                    "associated_token_program".to_string(),
+                ))?
+                .map_err(|_| {
+                    FuzzingError::CannotDeserializeAccount("associated_token_program".to_string())
+                })?;
         let system_program: anchor_lang::accounts::program::Program<System> = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts(
@@ -128,11 +172,13 @@ impl<'info> CreateVestingEscrowSnapshot<'info> {
         Ok(Self {
             base,
             escrow,
+            token_mint,
             escrow_token,
             sender,
             sender_token,
             recipient,
             token_program,
+            associated_token_program,
             system_program,
             event_authority,
             program,
@@ -154,13 +200,45 @@ impl<'info> ClaimSnapshot<'info> {
             .map(anchor_lang::accounts::account::Account::try_from)
             .ok_or(FuzzingError::AccountNotFound("escrow".to_string()))?
             .map_err(|_| FuzzingError::CannotDeserializeAccount("escrow".to_string()))?;
-        let escrow_token: anchor_lang::accounts::account::Account<TokenAccount> = accounts_iter
+        let vesting_schedule: Option<
+            anchor_lang::accounts::account::Account<
+                locker::state::vesting_schedule::VestingSchedule,
+            >,
+        > = accounts_iter
             .next()
-            .ok_or(FuzzingError::NotEnoughAccounts("escrow_token".to_string()))?
+            .ok_or(FuzzingError::NotEnoughAccounts(
+                "vesting_schedule".to_string(),
+            ))?
             .as_ref()
-            .map(anchor_lang::accounts::account::Account::try_from)
-            .ok_or(FuzzingError::AccountNotFound("escrow_token".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("escrow_token".to_string()))?;
+            .map(|acc| {
+                if acc.key() != *_program_id {
+                    anchor_lang::accounts::account::Account::try_from(acc).map_err(|_| {
+                        FuzzingError::CannotDeserializeAccount("vesting_schedule".to_string())
+                    })
+                } else {
+                    Err(FuzzingError::OptionalAccountNotProvided(
+                        "vesting_schedule".to_string(),
+                    ))
+                }
+            })
+            .transpose()
+            .unwrap_or(None);
+        let token_mint: anchor_lang::accounts::interface_account::InterfaceAccount<Mint> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("token_mint".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface_account::InterfaceAccount::try_from)
+                .ok_or(FuzzingError::AccountNotFound("token_mint".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("token_mint".to_string()))?;
+        let escrow_token: anchor_lang::accounts::interface_account::InterfaceAccount<TokenAccount> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("escrow_token".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface_account::InterfaceAccount::try_from)
+                .ok_or(FuzzingError::AccountNotFound("escrow_token".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("escrow_token".to_string()))?;
         let recipient: Signer<'_> = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts("recipient".to_string()))?
@@ -168,22 +246,59 @@ impl<'info> ClaimSnapshot<'info> {
             .map(anchor_lang::accounts::signer::Signer::try_from)
             .ok_or(FuzzingError::AccountNotFound("recipient".to_string()))?
             .map_err(|_| FuzzingError::CannotDeserializeAccount("recipient".to_string()))?;
-        let recipient_token: anchor_lang::accounts::account::Account<TokenAccount> = accounts_iter
+        let recipient_token: Option<
+            anchor_lang::accounts::interface_account::InterfaceAccount<TokenAccount>,
+        > = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts(
                 "recipient_token".to_string(),
             ))?
             .as_ref()
-            .map(anchor_lang::accounts::account::Account::try_from)
-            .ok_or(FuzzingError::AccountNotFound("recipient_token".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("recipient_token".to_string()))?;
-        let token_program: anchor_lang::accounts::program::Program<Token> = accounts_iter
+            .map(|acc| {
+                if acc.key() != *_program_id {
+                    anchor_lang::accounts::interface_account::InterfaceAccount::try_from(acc)
+                        .map_err(|_| {
+                            FuzzingError::CannotDeserializeAccount("recipient_token".to_string())
+                        })
+                } else {
+                    Err(FuzzingError::OptionalAccountNotProvided(
+                        "recipient_token".to_string(),
+                    ))
+                }
+            })
+            .transpose()
+            .unwrap_or(None);
+        let token_program: anchor_lang::accounts::interface::Interface<TokenInterface> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts("token_program".to_string()))?
+                .as_ref()
+                .map(anchor_lang::accounts::interface::Interface::try_from)
+                .ok_or(FuzzingError::AccountNotFound("token_program".to_string()))?
+                .map_err(|_| FuzzingError::CannotDeserializeAccount("token_program".to_string()))?;
+        let associated_token_program: anchor_lang::accounts::program::Program<AssociatedToken> =
+            accounts_iter
+                .next()
+                .ok_or(FuzzingError::NotEnoughAccounts(
+                    "associated_token_program".to_string(),
+                ))?
+                .as_ref()
+                .map(anchor_lang::accounts::program::Program::try_from)
+                .ok_or(FuzzingError::AccountNotFound(
+                    "associated_token_program".to_string(),
+                ))?
+                .map_err(|_| {
+                    FuzzingError::CannotDeserializeAccount("associated_token_program".to_string())
+                })?;
+        let system_program: anchor_lang::accounts::program::Program<System> = accounts_iter
             .next()
-            .ok_or(FuzzingError::NotEnoughAccounts("token_program".to_string()))?
+            .ok_or(FuzzingError::NotEnoughAccounts(
+                "system_program".to_string(),
+            ))?
             .as_ref()
             .map(anchor_lang::accounts::program::Program::try_from)
-            .ok_or(FuzzingError::AccountNotFound("token_program".to_string()))?
-            .map_err(|_| FuzzingError::CannotDeserializeAccount("token_program".to_string()))?;
+            .ok_or(FuzzingError::AccountNotFound("system_program".to_string()))?
+            .map_err(|_| FuzzingError::CannotDeserializeAccount("system_program".to_string()))?;
         let event_authority = accounts_iter
             .next()
             .ok_or(FuzzingError::NotEnoughAccounts(
@@ -198,10 +313,14 @@ impl<'info> ClaimSnapshot<'info> {
             .ok_or(FuzzingError::AccountNotFound("program".to_string()))?;
         Ok(Self {
             escrow,
+            vesting_schedule,
+            token_mint,
             escrow_token,
             recipient,
             recipient_token,
             token_program,
+            associated_token_program,
+            system_program,
             event_authority,
             program,
         })
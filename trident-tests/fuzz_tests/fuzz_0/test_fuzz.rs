@@ -8,6 +8,9 @@ use fuzz_instructions::locker_fuzz_instructions::FuzzInstruction as FuzzInstruct
 use trident_client::fuzzing::*;
 mod accounts_snapshots;
 mod fuzz_instructions;
+#[path = "../warp_client.rs"]
+mod warp_client;
+use warp_client::WarpClient;
 
 pub type FuzzInstruction = FuzzInstruction_locker;
 
@@ -48,7 +51,7 @@ fn main() {
             let fuzzing_program1 = FuzzingProgram::new(PROGRAM_NAME_LOCKER,&PROGRAM_ID_LOCKER,processor!(convert_entry!(entry_locker)));
 
             let mut client =
-                ProgramTestClientBlocking::new(&[fuzzing_program1])
+                WarpClient::new(&[fuzzing_program1])
                     .unwrap();
 
             // fill Program ID of program you are going to call
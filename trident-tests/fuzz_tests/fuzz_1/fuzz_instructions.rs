@@ -1,5 +1,6 @@
 pub mod locker_fuzz_instructions {
     use crate::accounts_snapshots::*;
+    use crate::warp_client::warp_clock;
     use locker::CreateVestingEscrowParameters;
     use solana_sdk::native_token::LAMPORTS_PER_SOL;
     use trident_client::fuzzing::*;
@@ -37,6 +38,9 @@ pub mod locker_fuzz_instructions {
     #[derive(Arbitrary, Debug)]
     pub struct CreateVestingEscrowData {
         pub params: locker::instructions::create_vesting_escrow::CreateVestingEscrowParameters,
+
+        // Manually added
+        pub stray_amount: Option<u64>,
     }
 
     #[derive(Arbitrary, Debug)]
@@ -63,6 +67,17 @@ pub mod locker_fuzz_instructions {
     #[derive(Arbitrary, Debug)]
     pub struct ClaimData {
         pub max_amount: u64,
+
+        // Manually added
+        pub warp_slots: u64,
+        pub create_recipient_token: bool,
+        // Pre-epoch clocks that test validators and forks can produce
+        #[arbitrary(
+            with = |u: &mut arbitrary::Unstructured| {
+                Ok(if u.arbitrary()? { Some(u.int_in_range(i64::MIN..=-1)?) } else { None })
+            }
+        )]
+        pub negative_unix_timestamp: Option<i64>,
     }
 
     #[derive(Arbitrary, Debug)]
@@ -130,6 +145,11 @@ pub mod locker_fuzz_instructions {
                     amount_per_period: self.data.params.amount_per_period,
                     number_of_period: self.data.params.number_of_period,
                     update_recipient_mode: 3,
+                    cancel_mode: self.data.params.cancel_mode,
+                    schedule_kind: self.data.params.schedule_kind,
+                    time_unit: self.data.params.time_unit,
+                    allow_crank_claim: self.data.params.allow_crank_claim,
+                    recipient_change_delay: self.data.params.recipient_change_delay,
                 },
             };
             Ok(data)
@@ -178,18 +198,21 @@ pub mod locker_fuzz_instructions {
                     &anchor_spl::associated_token::spl_associated_token_account::ID,
                 )
                 .unwrap();
-            client.set_account_custom(
-                &escrow_token.pubkey(),
-                &create_token_account_state(
-                    token_mint,
-                    escrow.pubkey(),
-                    10000000,
-                    None,
-                    None,
-                    0,
-                    Some(escrow.pubkey()),
-                ),
-            );
+            // the escrow ATA is created by the program, or already exists, possibly funded
+            if let Some(stray_amount) = self.data.stray_amount {
+                client.set_account_custom(
+                    &escrow_token.pubkey(),
+                    &create_token_account_state(
+                        token_mint,
+                        escrow.pubkey(),
+                        stray_amount,
+                        None,
+                        None,
+                        0,
+                        None,
+                    ),
+                );
+            }
 
             let sender = fuzz_accounts.sender.get_or_create_account(
                 self.accounts.sender,
@@ -211,6 +234,7 @@ pub mod locker_fuzz_instructions {
                 100 * LAMPORTS_PER_SOL,
             );
             let token_program = anchor_spl::token::ID;
+            let associated_token_program = anchor_spl::associated_token::ID;
             let system_program = anchor_lang::system_program::ID;
             let event_authority =
                 Pubkey::find_program_address(&[b"__event_authority"], &locker::ID).0;
@@ -219,11 +243,13 @@ pub mod locker_fuzz_instructions {
             let acc_meta = locker::accounts::CreateVestingEscrowCtx {
                 base: base.pubkey(),
                 escrow: escrow.pubkey(),
+                token_mint,
                 escrow_token: escrow_token.pubkey(),
                 sender: sender.pubkey(),
                 sender_token,
                 recipient: recipient.pubkey(),
                 token_program,
+                associated_token_program,
                 system_program,
                 event_authority,
                 program,
@@ -245,9 +271,20 @@ pub mod locker_fuzz_instructions {
                     return Err(FuzzingError::Custom(1));
                 }
 
-                if post_ix.escrow_token.amount != pre_ix.escrow_token.amount + deposit_amount {
+                let pre_escrow_token_amount = pre_ix
+                    .escrow_token
+                    .map_or(0, |escrow_token| escrow_token.amount);
+                let post_escrow_token_amount = post_ix
+                    .escrow_token
+                    .map_or(0, |escrow_token| escrow_token.amount);
+                if post_escrow_token_amount != deposit_amount {
                     return Err(FuzzingError::Custom(2));
                 }
+
+                // Check if an escrow ATA that already held tokens was rejected
+                if pre_escrow_token_amount != 0 {
+                    return Err(FuzzingError::Custom(10));
+                }
             }
 
             Ok(())
@@ -274,6 +311,14 @@ pub mod locker_fuzz_instructions {
             client: &mut impl FuzzClient,
             fuzz_accounts: &mut FuzzAccounts,
         ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+            // move time forward so claims reach the cliff and later periods of slot
+            // based escrows as well as timestamp based ones
+            warp_clock(
+                client,
+                self.data.warp_slots,
+                self.data.negative_unix_timestamp,
+            )?;
+
             let token_owner = Keypair::new();
             let token_mint = fuzz_accounts
                 .token_mint
@@ -317,40 +362,45 @@ pub mod locker_fuzz_instructions {
                     &anchor_spl::associated_token::spl_associated_token_account::ID,
                 )
                 .unwrap();
-            client.set_account_custom(
-                &escrow_token.pubkey(),
-                &create_token_account_state(
-                    token_mint,
-                    escrow.pubkey(),
-                    10000000,
-                    None,
-                    None,
-                    0,
-                    Some(escrow.pubkey()),
-                ),
-            );
-
-            let recipient_token = client.set_token_account(
-                token_mint,
-                recipient.pubkey(),
-                0,
-                None,
-                None,
-                0,
-                Some(recipient.pubkey()),
+            // the claim creates the recipient ATA unless it already exists
+            let recipient_token = anchor_spl::associated_token::get_associated_token_address(
+                &recipient.pubkey(),
+                &token_mint,
             );
+            if self.data.create_recipient_token
+                && client.get_account(&recipient_token).unwrap().is_none()
+            {
+                client.set_account_custom(
+                    &recipient_token,
+                    &create_token_account_state(
+                        token_mint,
+                        recipient.pubkey(),
+                        0,
+                        None,
+                        None,
+                        0,
+                        None,
+                    ),
+                );
+            }
 
             let token_program = anchor_spl::token::ID;
+            let associated_token_program = anchor_spl::associated_token::ID;
+            let system_program = anchor_lang::system_program::ID;
             let event_authority =
                 Pubkey::find_program_address(&[b"__event_authority"], &locker::ID).0;
             let program = locker::ID;
 
             let acc_meta = locker::accounts::ClaimCtx {
                 escrow: escrow.pubkey(),
+                vesting_schedule: None,
+                token_mint,
                 escrow_token: escrow_token.pubkey(),
                 recipient: recipient.pubkey(),
                 recipient_token,
                 token_program,
+                associated_token_program,
+                system_program,
                 event_authority,
                 program,
             }
@@ -363,11 +413,17 @@ pub mod locker_fuzz_instructions {
             &self,
             pre_ix: Self::IxSnapshot,
             post_ix: Self::IxSnapshot,
-            _ix_data: Self::IxData,
+            ix_data: Self::IxData,
         ) -> Result<(), FuzzingError> {
             // Check if the recipient has received tokens
             // Claim amount can be 0, thats why we use >
-            if pre_ix.recipient_token.amount > post_ix.recipient_token.amount {
+            let pre_recipient_token_amount = pre_ix
+                .recipient_token
+                .map_or(0, |recipient_token| recipient_token.amount);
+            let post_recipient_token_amount = post_ix
+                .recipient_token
+                .map_or(0, |recipient_token| recipient_token.amount);
+            if pre_recipient_token_amount > post_recipient_token_amount {
                 return Err(FuzzingError::Custom(3));
             }
 
@@ -376,10 +432,64 @@ pub mod locker_fuzz_instructions {
                 return Err(FuzzingError::Custom(4));
             }
 
+            // Check if the recipient received exactly the newly claimed amount
+            let claimed_amount =
+                post_ix.escrow.total_claimed_amount - pre_ix.escrow.total_claimed_amount;
+            if post_recipient_token_amount - pre_recipient_token_amount != claimed_amount {
+                return Err(FuzzingError::Custom(6));
+            }
+
+            // Check if claims never exceed the deposit, whichever schedule kind the escrow uses
+            if let Ok(deposit_amount) = post_ix.escrow.get_total_deposit_amount() {
+                if post_ix.escrow.total_claimed_amount > deposit_amount {
+                    return Err(FuzzingError::Custom(7));
+                }
+            }
+
+            // Check if claims are rejected while the clock is before the unix epoch,
+            // unless the escrow counts slots
+            if self.data.negative_unix_timestamp.is_some()
+                && post_ix.escrow.time_unit == u8::from(locker::TimeUnit::UnixTimestamp)
+            {
+                return Err(FuzzingError::Custom(8));
+            }
+
+            // Check if a claim that is not capped by max_amount leaves a PeriodicFromStart
+            // escrow at a released amount its schedule can produce
+            if post_ix.escrow.schedule_kind == u8::from(locker::ScheduleKind::PeriodicFromStart)
+                && claimed_amount < ix_data.max_amount
+                && !is_periodic_from_start_release(&post_ix.escrow)
+            {
+                return Err(FuzzingError::Custom(9));
+            }
+
             Ok(())
         }
     }
 
+    /// Whether `total_claimed_amount` is nothing, or the cliff amount plus a whole number of
+    /// periods counted from `vesting_start_time`, at least those accrued by the cliff.
+    fn is_periodic_from_start_release(escrow: &locker::VestingEscrow) -> bool {
+        let total_claimed_amount = escrow.total_claimed_amount;
+        if total_claimed_amount == 0 {
+            return true;
+        }
+        let Some(accrued_amount) = total_claimed_amount.checked_sub(escrow.cliff_unlock_amount)
+        else {
+            return false;
+        };
+        let periods_at_cliff = (escrow.cliff_time.saturating_sub(escrow.vesting_start_time)
+            / escrow.frequency)
+            .min(escrow.number_of_period);
+        if escrow.amount_per_period == 0 {
+            return accrued_amount == 0;
+        }
+        let periods = accrued_amount / escrow.amount_per_period;
+        accrued_amount % escrow.amount_per_period == 0
+            && periods >= periods_at_cliff
+            && periods <= escrow.number_of_period
+    }
+
     impl<'info> IxOps<'info> for CreateVestingEscrowMetadata {
         type IxData = locker::instruction::CreateVestingEscrowMetadata;
         type IxAccounts = FuzzAccounts;
@@ -393,8 +503,8 @@ pub mod locker_fuzz_instructions {
                 params: locker::CreateVestingEscrowMetadataParameters {
                     name: "dummy_name".to_owned(),
                     description: "dummy_description".to_owned(),
-                    creator_email: "dummy_creator_email".to_owned(),
-                    recipient_email: "dummy_recipient_email".to_owned(),
+                    creator_email: "creator@example.com".to_owned(),
+                    recipient_email: "recipient@example.com".to_owned(),
                 },
             };
             Ok(data)
@@ -464,7 +574,7 @@ pub mod locker_fuzz_instructions {
             );
             let data = locker::instruction::UpdateVestingEscrowRecipient {
                 new_recipient: new_recipient.pubkey(),
-                new_recipient_email: Some("dummy_email".to_owned()),
+                new_recipient_email: Some("new_recipient@example.com".to_owned()),
             };
             Ok(data)
         }
@@ -535,8 +645,13 @@ pub mod locker_fuzz_instructions {
             post_ix: Self::IxSnapshot,
             ix_data: Self::IxData,
         ) -> Result<(), FuzzingError> {
-            // Check if the recipient is updated
-            if post_ix.escrow.recipient != ix_data.new_recipient {
+            // Check if the recipient is updated, or scheduled when the escrow has a delay
+            let updated_recipient = if post_ix.escrow.recipient_change_delay == 0 {
+                post_ix.escrow.recipient
+            } else {
+                post_ix.escrow.pending_recipient
+            };
+            if updated_recipient != ix_data.new_recipient {
                 return Err(FuzzingError::Custom(5));
             }
 
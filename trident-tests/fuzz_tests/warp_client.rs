@@ -0,0 +1,192 @@
+//! [ProgramTestClientBlocking] with a clock that can be warped.
+//!
+//! `ProgramTestClientBlocking` writes every account through `ProgramTestContext::set_account`,
+//! which leaves the bank's sysvar cache untouched, so programs keep reading the genesis clock.
+//! This client routes writes to the clock sysvar through `ProgramTestContext::set_sysvar`
+//! instead, letting instructions warp slots and unix timestamps with
+//! [FuzzClient::set_account_custom].
+
+use anchor_spl::token::spl_token;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::rent::Rent;
+use solana_sdk::sysvar::{self, clock::Clock};
+use solana_sdk::transaction::VersionedTransaction;
+use trident_client::fuzzing::*;
+
+pub struct WarpClient {
+    ctx: ProgramTestContext,
+    rt: tokio::runtime::Runtime,
+}
+
+impl WarpClient {
+    /// Only builtin programs are supported, as every fuzz target here runs the locker natively.
+    pub fn new(programs: &[FuzzingProgram]) -> Result<Self, FuzzClientError> {
+        let mut program_test = ProgramTest::default();
+        for program in programs {
+            let entry = program
+                .entry
+                .unwrap_or_else(|| panic!("{} has no entry function", program.program_name));
+            program_test.add_builtin_program(&program.program_name, program.program_id, entry);
+        }
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let ctx = rt.block_on(program_test.start_with_context());
+        Ok(Self { ctx, rt })
+    }
+}
+
+impl FuzzClient for WarpClient {
+    fn set_account(&mut self, lamports: u64) -> Keypair {
+        let owner = Keypair::new();
+        let account = AccountSharedData::new(lamports, 0, &solana_sdk::system_program::ID);
+        self.ctx.set_account(&owner.pubkey(), &account);
+        owner
+    }
+
+    fn set_account_custom(&mut self, address: &Pubkey, account: &AccountSharedData) {
+        if *address == sysvar::clock::ID {
+            let clock: Clock =
+                solana_sdk::account::from_account(account).expect("invalid clock account");
+            self.ctx.set_sysvar(&clock);
+            return;
+        }
+        self.ctx.set_account(address, account);
+    }
+
+    fn set_token_account(
+        &mut self,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        delegate: Option<Pubkey>,
+        is_native: Option<u64>,
+        delegated_amount: u64,
+        close_authority: Option<Pubkey>,
+    ) -> Pubkey {
+        let token_account_key = Keypair::new().pubkey();
+
+        let token_account = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            delegate: delegate.into(),
+            state: spl_token::state::AccountState::Initialized,
+            is_native: is_native.into(),
+            delegated_amount,
+            close_authority: close_authority.into(),
+        };
+        let mut account = AccountSharedData::new(
+            Rent::default().minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN,
+            &spl_token::ID,
+        );
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(token_account, &mut data[..]).unwrap();
+        account.set_data_from_slice(&data);
+        self.ctx.set_account(&token_account_key, &account);
+
+        token_account_key
+    }
+
+    fn set_mint_account(
+        &mut self,
+        decimals: u8,
+        owner: &Pubkey,
+        freeze_authority: Option<Pubkey>,
+    ) -> Pubkey {
+        let mint_key = Keypair::new().pubkey();
+
+        let mint = spl_token::state::Mint {
+            is_initialized: true,
+            mint_authority: COption::Some(*owner),
+            freeze_authority: freeze_authority.into(),
+            decimals,
+            ..Default::default()
+        };
+        let mut account = AccountSharedData::new(
+            Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN,
+            &spl_token::ID,
+        );
+        let mut data = vec![0u8; spl_token::state::Mint::LEN];
+        spl_token::state::Mint::pack(mint, &mut data[..]).unwrap();
+        account.set_data_from_slice(&data);
+        self.ctx.set_account(&mint_key, &account);
+
+        mint_key
+    }
+
+    fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    fn get_account(&mut self, key: &Pubkey) -> Result<Option<Account>, FuzzClientError> {
+        Ok(self
+            .rt
+            .block_on(self.ctx.banks_client.get_account_with_commitment(
+                *key,
+                solana_sdk::commitment_config::CommitmentLevel::Confirmed,
+            ))?)
+    }
+
+    fn get_accounts(
+        &mut self,
+        metas: &[AccountMeta],
+    ) -> Result<Vec<Option<Account>>, FuzzClientErrorWithOrigin> {
+        metas
+            .iter()
+            .map(|meta| {
+                self.get_account(&meta.pubkey)
+                    .map_err(|e| e.with_origin(Origin::Account(meta.pubkey)))
+            })
+            .collect()
+    }
+
+    fn get_last_blockhash(&self) -> Hash {
+        self.ctx.last_blockhash
+    }
+
+    fn get_rent(&mut self) -> Result<Rent, FuzzClientError> {
+        Ok(self.rt.block_on(self.ctx.banks_client.get_rent())?)
+    }
+
+    fn process_transaction(
+        &mut self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<(), FuzzClientError> {
+        Ok(self
+            .rt
+            .block_on(self.ctx.banks_client.process_transaction(transaction))?)
+    }
+}
+
+/// Advance the clock by `slots`, moving the unix timestamp along at 400ms per slot
+/// unless an explicit `unix_timestamp` is given.
+pub fn warp_clock(
+    client: &mut impl FuzzClient,
+    slots: u64,
+    unix_timestamp: Option<i64>,
+) -> Result<(), FuzzingError> {
+    let account = client
+        .get_account(&sysvar::clock::ID)
+        .map_err(|_| FuzzingError::UnableToObtainData)?
+        .ok_or_else(|| FuzzingError::AccountNotFound(sysvar::clock::ID.to_string()))?;
+    let mut clock: Clock = solana_sdk::account::from_account(&account)
+        .ok_or_else(|| FuzzingError::CannotDeserializeAccount(sysvar::clock::ID.to_string()))?;
+
+    clock.slot = clock.slot.saturating_add(slots);
+    // about 400ms per slot, saturating rather than wrapping for huge warps
+    let elapsed = i64::try_from(slots / 5 * 2).unwrap_or(i64::MAX);
+    clock.unix_timestamp =
+        unix_timestamp.unwrap_or_else(|| clock.unix_timestamp.saturating_add(elapsed));
+
+    let account = AccountSharedData::new_data(account.lamports, &clock, &account.owner)
+        .map_err(|_| FuzzingError::DataMismatch)?;
+    client.set_account_custom(&sysvar::clock::ID, &account);
+    Ok(())
+}